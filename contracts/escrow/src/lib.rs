@@ -1,6 +1,10 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env};
+use reputation::ReputationContractClient;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, token, Address, Bytes, BytesN, Env,
+    IntoVal, Map, Symbol, Vec,
+};
 
 // TTL constants for persistent storage (in ledgers)
 // Note: Each ledger is approximately 5 seconds
@@ -13,9 +17,76 @@ const TTL_THRESHOLD: u32 = 17_280; // ~1 day - triggers extension when TTL drops
 pub struct Escrow {
     pub client: Address,
     pub artisan: Address,
+    /// The Stellar asset this engagement settles in, captured once at
+    /// `initialize` and used by every subsequent transfer.
+    pub token: Address,
     pub amount: i128,
     pub status: Status,
     pub deadline: u64,
+    pub vesting: Option<VestingConfig>,
+    pub released_so_far: i128,
+    pub arbiter: Option<Address>,
+    /// When set, `release_cross_chain` pays out to a relayer on another
+    /// chain instead of transferring directly to `artisan` on Stellar.
+    pub destination: Option<CrossChainDest>,
+    /// When set, idle funds are deposited into this staking contract while
+    /// the escrow is `Funded`, rather than sitting unstaked.
+    pub staking_contract: Option<Address>,
+    /// Whether the escrow's funds are currently deposited with
+    /// `staking_contract`. Cleared once `release`/`reclaim` or a dispute
+    /// resolution (`resolve`/`resolve_dispute`/`resolve_by_bps`) withdraws
+    /// them.
+    pub staked: bool,
+    /// Who receives any yield earned above `amount` on withdrawal from
+    /// `staking_contract`. Defaults to `client` when unset.
+    pub yield_beneficiary: Option<Address>,
+}
+
+/// Names the bridged-chain payout for an engagement settled off-network.
+/// `order_hash` uniquely identifies the settlement order to the relayer
+/// watching for `CrossChainOrderEvent` and is also the `PendingOrder` key.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CrossChainDest {
+    pub chain_id: u32,
+    pub recipient: BytesN<32>,
+    pub order_hash: BytesN<32>,
+}
+
+/// Funds locked by `release_cross_chain`, awaiting `claim_order` by the
+/// registered relayer or `cancel_order` by the client after `expiry`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingOrder {
+    pub engagement_id: u64,
+    pub client: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub expiry: u64,
+}
+
+/// Optional milestone-based vesting schedule attached to an escrow.
+///
+/// Vesting unlocks linearly between `start` and `end`, with nothing claimable
+/// before `cliff`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingConfig {
+    pub start: u64,
+    pub cliff: u64,
+    pub end: u64,
+}
+
+/// A single staged payout within a milestone escrow. Unlike `VestingConfig`'s
+/// continuous curve, each milestone is its own fixed amount, settled
+/// independently once via `release_milestone` (to the artisan) or
+/// `reclaim_milestone` (back to the client, after its own `deadline` passes).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub amount: i128,
+    pub deadline: u64,
+    pub released: bool,
 }
 
 #[contracttype]
@@ -23,9 +94,15 @@ pub struct Escrow {
 pub enum Status {
     Pending,
     Funded,
+    Vesting,
     Released,
     Refunded, // added for reclaimed/returned escrows
     Disputed,
+    // Locked under a pending cross-chain order by `release_cross_chain`,
+    // awaiting relayer payout confirmation via `claim_order`. Moves to
+    // `Released` once claimed, or back to `Funded` if `cancel_order` fires
+    // after the order expires unclaimed.
+    CrossChainPending,
 }
 
 #[contracttype]
@@ -33,6 +110,117 @@ pub enum Status {
 pub enum DataKey {
     Escrow(u64),
     NextId,
+    // Per-engagement map of funder address -> amount contributed so far, allowing
+    // an escrow to be crowd-funded by more than one depositor.
+    Funders(u64),
+    // Append-only MMR audit trail: all nodes (leaves and internal), position-indexed.
+    MmrNodes,
+    // Height of each node in `MmrNodes`, in the same position order.
+    MmrHeights,
+    // Positions (into `MmrNodes`) of the current mountain peaks, left to right.
+    MmrPeaks,
+    // Count of leaves appended so far; doubles as each new leaf's ordinal,
+    // which is what `verify_proof`'s `leaf_index` refers to.
+    MmrLeafCount,
+    // Admin address permitted to update `FeeConfig`, the token allow-list and
+    // the relayer; bootstrapped by whoever first calls any of the admin-gated
+    // setters (`set_fee_config`, `allow_token`, `disallow_token`,
+    // `set_allowed_tokens`, `register_relayer`).
+    Admin,
+    // Presence of this key marks `token` as an asset `initialize` may accept.
+    // Stores `true`; managed by the admin via `allow_token`/`disallow_token`.
+    AllowedToken(Address),
+    // Set once the admin calls `allow_token` or `set_allowed_tokens` for the
+    // first time. Before that,
+    // the allow-list is considered unconfigured and any token is accepted,
+    // so existing standalone deployments aren't broken by opting in later.
+    TokenAllowlistActive,
+    // Protocol fee configuration applied on `release`. Dispute resolution
+    // (`resolve`/`resolve_dispute`) and `reclaim` are fee-exempt.
+    FeeConfig,
+    // Per-engagement reputation contract address, optionally configured at
+    // `initialize`, used to record completed engagements and client ratings.
+    ReputationContract(u64),
+    // Per-engagement conditional payment plan, optionally configured at
+    // `initialize` and consumed by `apply_timestamp`/`apply_signature`.
+    Plan(u64),
+    // Per-engagement list of staged milestone payouts, optionally configured
+    // at `initialize` and settled piecewise by `release_milestone`/`reclaim_milestone`.
+    Milestones(u64),
+    // Relayer address permitted to `claim_order`; bootstrapped the same way
+    // as `Admin`, by whoever calls `register_relayer` first.
+    Relayer,
+    // Funds locked by `release_cross_chain`, keyed by the order's unique
+    // hash, awaiting `claim_order` or `cancel_order`.
+    PendingOrder(BytesN<32>),
+    // Bulk snapshot of the allow-listed tokens, set wholesale via
+    // `set_allowed_tokens`, which also writes an `AllowedToken` entry for
+    // each listed token. `initialize` checks only those `AllowedToken`
+    // entries, not this list.
+    AllowedTokens,
+}
+
+/// A single condition a payment plan step can be waiting on.
+#[contracttype]
+#[derive(Clone)]
+pub enum Condition {
+    /// Satisfied once `env.ledger().timestamp()` reaches the given value.
+    Timestamp(u64),
+    /// Satisfied once the named address authorizes the call.
+    Signature(Address),
+}
+
+/// A transfer of `amount` to `to`, fired once its governing condition holds.
+#[contracttype]
+#[derive(Clone)]
+pub struct Payment {
+    pub amount: i128,
+    pub to: Address,
+}
+
+/// A `Payment` paired with the `Condition` that releases it.
+#[contracttype]
+#[derive(Clone)]
+pub struct ConditionalPayment {
+    pub condition: Condition,
+    pub payment: Payment,
+}
+
+/// A composable payment plan, evaluated on each `apply_timestamp`/
+/// `apply_signature` call: `Pay` fires immediately, `After` fires once its
+/// single condition holds, and `Or` fires whichever of its two branches'
+/// condition is met first.
+#[contracttype]
+#[derive(Clone)]
+pub enum Plan {
+    Pay(Payment),
+    After(ConditionalPayment),
+    Or(ConditionalPayment, ConditionalPayment),
+}
+
+/// The optional fields `initialize` accepts beyond the five required ones
+/// (`client`, `artisan`, `amount`, `deadline`, `token`), bagged into a single
+/// struct so the entrypoint stays under Soroban's 10-parameter arity cap.
+#[contracttype]
+#[derive(Clone, Default)]
+pub struct EngagementOptions {
+    pub vesting: Option<VestingConfig>,
+    pub arbiter: Option<Address>,
+    pub reputation_contract: Option<Address>,
+    pub plan: Option<Plan>,
+    pub milestones: Option<Vec<Milestone>>,
+    pub destination: Option<CrossChainDest>,
+    pub staking_contract: Option<Address>,
+    pub yield_beneficiary: Option<Address>,
+}
+
+/// Protocol fee skimmed to a configurable treasury whenever funds are
+/// released to the artisan, expressed in basis points (1/100th of a percent).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub treasury: Address,
+    pub bps: u32,
 }
 
 #[contracttype]
@@ -42,6 +230,16 @@ pub struct EngagementInitializedEvent {
     pub artisan: Address,
 }
 
+// Event emitted when `fund` pulls the remaining shortfall from the client via
+// a pre-approved allowance and transitions the escrow to `Funded`; `amount`
+// is the escrow's full amount, not just the part `fund` pulled
+#[contracttype]
+pub struct FundedEvent {
+    pub id: u64,
+    pub client: Address,
+    pub amount: i128,
+}
+
 // Event emitted when a funded escrow is reclaimed by the client after the deadline
 #[contracttype]
 pub struct ReclaimedEvent {
@@ -51,213 +249,2098 @@ pub struct ReclaimedEvent {
     pub timestamp: u64,
 }
 
-#[contract]
-pub struct EscrowContract;
+// Event emitted each time a vesting claim pays out a newly-unlocked slice
+#[contracttype]
+pub struct ClaimedEvent {
+    pub id: u64,
+    pub artisan: Address,
+    pub amount: i128,
+    pub released_so_far: i128,
+}
+
+// Event emitted when a client terminates a vesting escrow early
+#[contracttype]
+pub struct TerminatedEvent {
+    pub id: u64,
+    pub client: Address,
+    pub vested_to_artisan: i128,
+    pub refunded_to_client: i128,
+}
+
+// Event emitted when `terminate_milestones` refunds the unreleased portion of
+// a milestone-based escrow back to the client early
+#[contracttype]
+pub struct MilestonesTerminatedEvent {
+    pub id: u64,
+    pub client: Address,
+    pub refunded_to_client: i128,
+}
+
+// Event emitted when a client or artisan opens a dispute on a funded escrow
+#[contracttype]
+pub struct DisputedEvent {
+    pub id: u64,
+    pub opened_by: Address,
+}
+
+// Event emitted when the arbiter resolves a disputed escrow
+#[contracttype]
+pub struct ResolvedEvent {
+    pub id: u64,
+    pub arbiter: Address,
+    pub to_artisan: bool,
+    pub amount: i128,
+}
+
+// Event emitted when the arbiter resolves a disputed escrow with a split
+// between both parties, via `resolve_dispute`
+#[contracttype]
+pub struct DisputeResolvedEvent {
+    pub id: u64,
+    pub arbiter: Address,
+    pub to_artisan: i128,
+    pub to_client: i128,
+}
+
+// Event emitted when a conditional payment plan fires its `Payment`
+#[contracttype]
+pub struct PlanExecutedEvent {
+    pub id: u64,
+    pub to: Address,
+    pub amount: i128,
+}
+
+// Event emitted when a single milestone's funds are released to the artisan
+#[contracttype]
+pub struct MilestoneReleasedEvent {
+    pub id: u64,
+    pub index: u32,
+    pub amount: i128,
+}
+
+// Event emitted when a single milestone's funds are reclaimed by the client
+// after that milestone's own deadline has passed
+#[contracttype]
+pub struct MilestoneReclaimedEvent {
+    pub id: u64,
+    pub index: u32,
+    pub amount: i128,
+}
+
+// Event emitted when `release_cross_chain` locks funds for an off-chain
+// relayer to watch and fulfill on the destination chain
+#[contracttype]
+pub struct CrossChainOrderEvent {
+    pub id: u64,
+    pub chain_id: u32,
+    pub recipient: BytesN<32>,
+    pub order_hash: BytesN<32>,
+    pub amount: i128,
+}
+
+// Event emitted whenever an escrow's status changes, via `set_status`.
+// Published alongside the other, longer-standing per-action events
+// (`ReclaimedEvent`, `ResolvedEvent`, etc.); it does not replace them.
+#[contracttype]
+pub struct StatusChangedEvent {
+    pub id: u64,
+    pub from: Status,
+    pub to: Status,
+}
+
+// Event emitted when `set_allowed_tokens` replaces the bulk token allow-list
+#[contracttype]
+pub struct AllowedTokensChangedEvent {
+    pub tokens: Vec<Address>,
+}
+
+// Event emitted when the registered relayer claims a pending cross-chain order
+#[contracttype]
+pub struct OrderClaimedEvent {
+    pub order_hash: BytesN<32>,
+    pub relayer: Address,
+    pub amount: i128,
+}
+
+// Event emitted when a pending cross-chain order expires unclaimed and its
+// funds are returned to the client
+#[contracttype]
+pub struct OrderCancelledEvent {
+    pub order_hash: BytesN<32>,
+    pub amount: i128,
+}
+
+// --- Merkle Mountain Range audit trail -------------------------------------
+//
+// Every state-changing entrypoint appends a leaf `H(event_bytes)` to an
+// append-only MMR so off-chain indexers and light clients can later prove
+// that a given transition occurred without trusting the indexer. Nodes
+// (leaves and internal parents alike) are stored in a single position-indexed
+// vector; `MmrPeaks` tracks the positions of the current mountain peaks,
+// left to right.
+
+/// Combine two child hashes into their parent: `H(left || right)`.
+fn mmr_hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut bytes = Bytes::from_array(env, &left.to_array());
+    bytes.append(&Bytes::from_array(env, &right.to_array()));
+    env.crypto().sha256(&bytes).into()
+}
+
+/// Encode a state-transition event as bytes ahead of hashing into a leaf.
+fn mmr_event_bytes(env: &Env, kind: &str, engagement_id: u64, amount: i128) -> Bytes {
+    let mut bytes = Bytes::from_array(env, &(kind.len() as u32).to_be_bytes());
+    bytes.append(&Bytes::from_slice(env, kind.as_bytes()));
+    bytes.append(&Bytes::from_array(env, &engagement_id.to_be_bytes()));
+    bytes.append(&Bytes::from_array(env, &amount.to_be_bytes()));
+    bytes.append(&Bytes::from_array(env, &env.ledger().timestamp().to_be_bytes()));
+    bytes
+}
+
+/// Append a leaf for `kind` (e.g. "initialize", "deposit", "release", "refund")
+/// to the MMR, merging equal-height peaks and recomputing the peak list.
+/// Returns the leaf's ordinal (0-based count of leaves appended before it),
+/// which is the `leaf_index` that `verify_proof` expects: because mountains
+/// are merged greedily exactly like a binary counter increment, a leaf's
+/// position within its own mountain is given by the low bits of its ordinal.
+fn mmr_append(env: &Env, kind: &str, engagement_id: u64, amount: i128) -> u64 {
+    let leaf: BytesN<32> = env
+        .crypto()
+        .sha256(&mmr_event_bytes(env, kind, engagement_id, amount))
+        .into();
+
+    let mut nodes: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MmrNodes)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut heights: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MmrHeights)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut peaks: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MmrPeaks)
+        .unwrap_or_else(|| Vec::new(env));
+    let leaf_index: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::MmrLeafCount)
+        .unwrap_or(0u64);
+
+    nodes.push_back(leaf);
+    heights.push_back(0);
+    peaks.push_back(nodes.len() - 1);
+
+    // While the newest peak has a left sibling of equal height, merge them
+    // into a parent and keep bubbling up.
+    while peaks.len() >= 2 {
+        let right_pos = peaks.get(peaks.len() - 1).unwrap();
+        let left_pos = peaks.get(peaks.len() - 2).unwrap();
+        let right_height = heights.get(right_pos).unwrap();
+        let left_height = heights.get(left_pos).unwrap();
+        if left_height != right_height {
+            break;
+        }
+
+        let parent = mmr_hash_pair(env, &nodes.get(left_pos).unwrap(), &nodes.get(right_pos).unwrap());
+        let parent_pos = nodes.len();
+        nodes.push_back(parent);
+        heights.push_back(left_height + 1);
+
+        peaks.remove(peaks.len() - 1);
+        peaks.remove(peaks.len() - 1);
+        peaks.push_back(parent_pos);
+    }
+
+    env.storage().persistent().set(&DataKey::MmrNodes, &nodes);
+    env.storage().persistent().set(&DataKey::MmrHeights, &heights);
+    env.storage().persistent().set(&DataKey::MmrPeaks, &peaks);
+    env.storage()
+        .persistent()
+        .set(&DataKey::MmrLeafCount, &(leaf_index + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::MmrNodes, TTL_THRESHOLD, ESCROW_TTL);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::MmrHeights, TTL_THRESHOLD, ESCROW_TTL);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::MmrPeaks, TTL_THRESHOLD, ESCROW_TTL);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::MmrLeafCount, TTL_THRESHOLD, ESCROW_TTL);
+
+    leaf_index
+}
+
+/// Bag a left-to-right list of peak hashes into a single root by folding
+/// right-to-left: `acc = H(peak, acc)`.
+fn mmr_bag_peaks(env: &Env, peaks: &Vec<BytesN<32>>) -> BytesN<32> {
+    let n = peaks.len();
+    let mut acc = peaks.get(n - 1).unwrap();
+    let mut i = n - 1;
+    while i > 0 {
+        i -= 1;
+        acc = mmr_hash_pair(env, &peaks.get(i).unwrap(), &acc);
+    }
+    acc
+}
+
+/// Bootstrap `admin` as the contract's admin if none is set yet, otherwise
+/// require that `admin` matches the already-bootstrapped address. Shared by
+/// every admin-gated setter (`set_fee_config`, `allow_token`, ...) so the
+/// first caller of any of them becomes the one admin for all of them.
+fn bootstrap_or_require_admin(env: &Env, admin: &Address, message: &'static str) {
+    let admin_key = DataKey::Admin;
+    let stored_admin: Option<Address> = env.storage().persistent().get(&admin_key);
+    match stored_admin {
+        Some(existing) => {
+            if &existing != admin {
+                panic!("{}", message);
+            }
+        }
+        None => {
+            env.storage().persistent().set(&admin_key, admin);
+            env.storage()
+                .persistent()
+                .extend_ttl(&admin_key, TTL_THRESHOLD, NEXT_ID_TTL);
+        }
+    }
+}
+
+/// The states reachable from `status` in one step, per the transition table
+/// implied by the existing entrypoints (`deposit`/`fund`, `release`,
+/// `reclaim`/`terminate`/`terminate_milestones`, `claim`, `dispute`,
+/// `resolve`/`resolve_dispute`/`resolve_by_bps`, `release_cross_chain`,
+/// `claim_order`/`cancel_order`). `Released` and `Refunded` are terminal.
+fn allowed_next_states(env: &Env, status: &Status) -> Vec<Status> {
+    match status {
+        Status::Pending => Vec::from_array(env, [Status::Funded]),
+        Status::Funded => Vec::from_array(
+            env,
+            [
+                Status::Vesting,
+                Status::Released,
+                Status::Refunded,
+                Status::Disputed,
+                Status::CrossChainPending,
+            ],
+        ),
+        Status::Vesting => Vec::from_array(env, [Status::Vesting, Status::Released, Status::Refunded]),
+        // `resolve`/`resolve_dispute`/`resolve_by_bps` can land on either
+        // terminal status depending on how the arbiter splits the funds: all
+        // to the artisan (or any split that gives them something) settles as
+        // `Released`, an all-to-client split settles as `Refunded`.
+        Status::Disputed => Vec::from_array(env, [Status::Released, Status::Refunded]),
+        Status::CrossChainPending => Vec::from_array(env, [Status::Released, Status::Funded]),
+        Status::Released => Vec::new(env),
+        Status::Refunded => Vec::new(env),
+    }
+}
+
+/// Error returned by `assert_transition` when a requested status change
+/// isn't reachable per `allowed_next_states`.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EscrowError {
+    InvalidStatusTransition = 1,
+}
+
+/// `Err(EscrowError::InvalidStatusTransition)` unless `to` is reachable from
+/// `from` per `allowed_next_states`. The table-driven status check used by
+/// `release`/`reclaim`/`dispute`/`resolve`/`resolve_dispute`/`resolve_by_bps`
+/// and the milestone/cross-chain settlement paths in place of an ad hoc
+/// `status != X` guard; also backs the introspection API
+/// (`allowed_next_states`/`get_escrow_status`).
+fn assert_transition(env: &Env, from: &Status, to: &Status) -> Result<(), EscrowError> {
+    if allowed_next_states(env, from).iter().any(|s| &s == to) {
+        Ok(())
+    } else {
+        Err(EscrowError::InvalidStatusTransition)
+    }
+}
+
+/// Set `escrow.status` to `to` and emit a `StatusChangedEvent` recording the
+/// transition, so every status change is observable the same way regardless
+/// of which entrypoint drove it.
+fn set_status(env: &Env, engagement_id: u64, escrow: &mut Escrow, to: Status) {
+    let from = escrow.status.clone();
+    escrow.status = to.clone();
+    env.events()
+        .publish((), StatusChangedEvent { id: engagement_id, from, to });
+}
+
+/// Pay `amount` out to `artisan`, skimming the configured protocol fee (if
+/// any) to the treasury first. Returns the amount the artisan actually
+/// received. Fee math is `fee = amount * bps / 10_000`, so `fee +
+/// to_artisan == amount` exactly — no rounding dust is left stranded in the
+/// contract.
+fn apply_fee(env: &Env, token_client: &token::Client, amount: i128, artisan: &Address) -> i128 {
+    let config: Option<FeeConfig> = env.storage().persistent().get(&DataKey::FeeConfig);
+    match config {
+        Some(cfg) if cfg.bps > 0 => {
+            let fee = (amount * cfg.bps as i128) / 10_000;
+            let to_artisan = amount - fee;
+            if fee > 0 {
+                token_client.transfer(&env.current_contract_address(), &cfg.treasury, &fee);
+            }
+            if to_artisan > 0 {
+                token_client.transfer(&env.current_contract_address(), artisan, &to_artisan);
+            }
+            to_artisan
+        }
+        _ => {
+            token_client.transfer(&env.current_contract_address(), artisan, &amount);
+            amount
+        }
+    }
+}
+
+/// If a reputation contract is configured for `engagement_id`, record a
+/// completed engagement for `artisan` via a cross-contract call. Standalone
+/// escrows with no reputation contract configured are unaffected.
+fn notify_reputation_contract(env: &Env, engagement_id: u64, artisan: &Address) {
+    let key = DataKey::ReputationContract(engagement_id);
+    let contract_address: Option<Address> = env.storage().persistent().get(&key);
+    if let Some(contract_address) = contract_address {
+        let client = ReputationContractClient::new(env, &contract_address);
+        client.record_engagement(artisan);
+    }
+}
+
+/// If `escrow` names a `staking_contract` and isn't already staked, approve
+/// it to pull the idle `amount` and invoke `deposit_and_stake(from, token,
+/// amount)` so it can stake it. Uses `try_invoke_contract` so a failing or
+/// missing staking contract simply leaves the (still-approved, still-held)
+/// funds unstaked in the escrow contract, rather than blocking the funding
+/// flow that called this.
+fn stake_if_configured(env: &Env, escrow: &mut Escrow) {
+    let Some(staking_contract) = escrow.staking_contract.clone() else {
+        return;
+    };
+    if escrow.staked {
+        return;
+    }
+
+    let contract_address = env.current_contract_address();
+    let token_client = token::Client::new(env, &escrow.token);
+    let expiration_ledger = env.ledger().sequence() + ESCROW_TTL;
+    token_client.approve(
+        &contract_address,
+        &staking_contract,
+        &escrow.amount,
+        &expiration_ledger,
+    );
+
+    let fn_name = Symbol::new(env, "deposit_and_stake");
+    let args: Vec<soroban_sdk::Val> = Vec::from_array(
+        env,
+        [
+            contract_address.into_val(env),
+            escrow.token.clone().into_val(env),
+            escrow.amount.into_val(env),
+        ],
+    );
+    let result: Result<
+        Result<(), soroban_sdk::ConversionError>,
+        Result<soroban_sdk::Error, soroban_sdk::InvokeError>,
+    > = env.try_invoke_contract(&staking_contract, &fn_name, args);
+    if let Ok(Ok(())) = result {
+        escrow.staked = true;
+    }
+}
+
+/// If `escrow` is currently staked, withdraw its principal plus any yield
+/// from `staking_contract` via `withdraw(to, token, amount) -> i128`, pay the
+/// yield (anything above `amount`) to `yield_beneficiary` (or `client` if
+/// unset), and clear `staked`. Uses `try_invoke_contract` so a failing
+/// staking contract leaves `staked` set and the escrow unchanged, still
+/// recoverable by retrying `release`/`reclaim` later.
+fn unstake_if_staked(env: &Env, escrow: &mut Escrow) {
+    if !escrow.staked {
+        return;
+    }
+    let Some(staking_contract) = escrow.staking_contract.clone() else {
+        return;
+    };
+
+    let contract_address = env.current_contract_address();
+    let fn_name = Symbol::new(env, "withdraw");
+    let args: Vec<soroban_sdk::Val> = Vec::from_array(
+        env,
+        [
+            contract_address.clone().into_val(env),
+            escrow.token.clone().into_val(env),
+            escrow.amount.into_val(env),
+        ],
+    );
+    let result: Result<
+        Result<i128, soroban_sdk::ConversionError>,
+        Result<soroban_sdk::Error, soroban_sdk::InvokeError>,
+    > = env.try_invoke_contract(&staking_contract, &fn_name, args);
+    if let Ok(Ok(withdrawn)) = result {
+        escrow.staked = false;
+        let yield_amount = withdrawn - escrow.amount;
+        if yield_amount > 0 {
+            let beneficiary = escrow
+                .yield_beneficiary
+                .clone()
+                .unwrap_or_else(|| escrow.client.clone());
+            let token_client = token::Client::new(env, &escrow.token);
+            token_client.transfer(&env.current_contract_address(), &beneficiary, &yield_amount);
+        }
+    }
+}
+
+/// Panic if `escrow` was configured for one of the specialized release
+/// paths (vesting, milestones, a payment plan, or a cross-chain
+/// destination), so `release`/`reclaim`'s plain all-or-nothing transfer
+/// can't be used to bypass the schedule or condition those paths enforce.
+fn assert_no_specialized_release_path(env: &Env, engagement_id: u64, escrow: &Escrow) {
+    if escrow.vesting.is_some() {
+        panic!("Escrow has a vesting schedule; use claim/terminate instead");
+    }
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Milestones(engagement_id))
+    {
+        panic!("Escrow has a milestone schedule; use release_milestone/reclaim_milestone/terminate_milestones instead");
+    }
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Plan(engagement_id))
+    {
+        panic!("Escrow has a payment plan; use apply_timestamp/apply_signature instead");
+    }
+    if escrow.destination.is_some() {
+        panic!("Escrow has a cross-chain destination; use release_cross_chain instead");
+    }
+}
+
+/// What triggered this evaluation of a stored `Plan`: either a timestamp
+/// check or a signer presenting their authorization.
+enum PlanTrigger {
+    Timestamp(u64),
+    Signature(Address),
+}
+
+/// Does `condition` hold given `trigger`? A `Signature` condition matches
+/// only the exact address that authorized this call.
+fn condition_met(condition: &Condition, trigger: &PlanTrigger) -> bool {
+    match (condition, trigger) {
+        (Condition::Timestamp(ts), PlanTrigger::Timestamp(now)) => now >= ts,
+        (Condition::Signature(signer), PlanTrigger::Signature(caller)) => signer == caller,
+        _ => false,
+    }
+}
+
+/// Transfer `payment.amount` to `payment.to`, skimming the protocol fee if
+/// the recipient is the artisan (matching a plain `release`), and mark the
+/// escrow settled.
+fn execute_payment(env: &Env, engagement_id: u64, escrow: &mut Escrow, payment: &Payment) {
+    let token_client = token::Client::new(env, &escrow.token);
+    if payment.to == escrow.artisan {
+        apply_fee(env, &token_client, payment.amount, &escrow.artisan);
+        set_status(env, engagement_id, escrow, Status::Released);
+    } else {
+        token_client.transfer(&env.current_contract_address(), &payment.to, &payment.amount);
+        set_status(env, engagement_id, escrow, Status::Refunded);
+    }
+
+    let key = DataKey::Escrow(engagement_id);
+    env.storage().persistent().set(&key, escrow);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+    env.storage().persistent().remove(&DataKey::Plan(engagement_id));
+
+    env.events().publish((), PlanExecutedEvent {
+        id: engagement_id,
+        to: payment.to.clone(),
+        amount: payment.amount,
+    });
+
+    mmr_append(env, "plan_executed", engagement_id, payment.amount);
+    if payment.to == escrow.artisan {
+        notify_reputation_contract(env, engagement_id, &escrow.artisan);
+    }
+}
+
+/// Evaluate the plan stored for `engagement_id` against `trigger`, executing
+/// and consuming it if a branch's condition is met. Returns `true` if a
+/// payment fired, `false` if the plan is still pending (or none exists).
+fn try_execute_plan(env: &Env, engagement_id: u64, trigger: PlanTrigger) -> bool {
+    let plan_key = DataKey::Plan(engagement_id);
+    let plan: Option<Plan> = env.storage().persistent().get(&plan_key);
+    let plan = match plan {
+        Some(plan) => plan,
+        None => return false,
+    };
+
+    let key = DataKey::Escrow(engagement_id);
+    let mut escrow: Escrow = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .expect("Escrow not found");
+
+    if escrow.status != Status::Funded {
+        panic!("Escrow must be Funded for its payment plan to execute");
+    }
+
+    let payment = match &plan {
+        Plan::Pay(payment) => Some(payment.clone()),
+        Plan::After(cp) => {
+            if condition_met(&cp.condition, &trigger) {
+                Some(cp.payment.clone())
+            } else {
+                None
+            }
+        }
+        Plan::Or(first, second) => {
+            if condition_met(&first.condition, &trigger) {
+                Some(first.payment.clone())
+            } else if condition_met(&second.condition, &trigger) {
+                Some(second.payment.clone())
+            } else {
+                None
+            }
+        }
+    };
+
+    match payment {
+        Some(payment) => {
+            execute_payment(env, engagement_id, &mut escrow, &payment);
+            true
+        }
+        None => false,
+    }
+}
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Initialize a new escrow engagement
+    /// Creates a new escrow record with Pending status.
+    ///
+    /// `token` is the Stellar asset this engagement settles in; it's captured
+    /// once here and used by every subsequent transfer, so callers no longer
+    /// pass a (possibly mismatched) token into `deposit`/`release`/`reclaim`
+    /// themselves. Rejected if the admin has opted into a token allow-list
+    /// via `allow_token` and `token` isn't on it.
+    ///
+    /// `options` bags up every optional configuration field, kept out of the
+    /// positional parameter list to stay under Soroban's 10-parameter
+    /// entrypoint cap:
+    /// `vesting` optionally attaches a milestone-based release schedule; when set,
+    /// the artisan draws funds progressively via `claim` instead of a single `release`.
+    /// `arbiter` optionally names a neutral third party who can settle a dispute
+    /// via `resolve` once either party calls `dispute`.
+    /// `plan` optionally attaches a conditional payment plan, settled via
+    /// `apply_timestamp`/`apply_signature` instead of the plain `release`/`reclaim` flow.
+    /// `milestones` optionally attaches a list of staged payouts settled piecewise via
+    /// `release_milestone`/`reclaim_milestone`; their amounts must sum to `amount`.
+    /// `destination` optionally names a bridged-chain payout for the artisan,
+    /// settled via `release_cross_chain` instead of the plain `release`.
+    /// `staking_contract` optionally names a staking pool idle funds are
+    /// deposited into while `Funded`, withdrawn automatically by `release`/
+    /// `reclaim`/`resolve`/`resolve_dispute`/`resolve_by_bps`;
+    /// `yield_beneficiary` names who keeps any yield earned above `amount`
+    /// (defaults to `client`).
+    ///
+    /// At most one of `vesting`/`milestones`/`plan`/`destination` may be set:
+    /// each is a distinct, full-balance-consuming settlement path, and the
+    /// others' entrypoints (`release`, `release_cross_chain`, ...) only check
+    /// their own path, not whether a competing one is also configured.
+    /// `staking_contract` may not be combined with any of them either: those
+    /// paths pay out piecewise or via a destination `unstake_if_staked`
+    /// doesn't know about, so a staking pool can only be paired with the
+    /// plain `release`/`reclaim`/dispute-resolution flow.
+    pub fn initialize(
+        env: Env,
+        client: Address,
+        artisan: Address,
+        amount: i128,
+        deadline: u64,
+        token: Address,
+        options: EngagementOptions,
+    ) -> u64 {
+        let EngagementOptions {
+            vesting,
+            arbiter,
+            reputation_contract,
+            plan,
+            milestones,
+            destination,
+            staking_contract,
+            yield_beneficiary,
+        } = options;
+
+        // Validation: client cannot be the same as artisan
+        if client == artisan {
+            panic!("Client and artisan cannot be the same address");
+        }
+
+        // Validation: amount must be positive
+        if amount <= 0 {
+            panic!("Amount must be greater than zero");
+        }
+
+        // Validation: once the admin has opted into a token allow-list via
+        // `allow_token`, only vetted assets may be used for new engagements.
+        let allowlist_active: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TokenAllowlistActive)
+            .unwrap_or(false);
+        if allowlist_active {
+            let allowed: bool = env
+                .storage()
+                .persistent()
+                .get(&DataKey::AllowedToken(token.clone()))
+                .unwrap_or(false);
+            if !allowed {
+                panic!("Token is not on the allow-list");
+            }
+        }
+
+        // Validation: a vesting schedule must have start <= cliff <= end and make progress
+        if let Some(v) = &vesting {
+            if !(v.start <= v.cliff && v.cliff <= v.end && v.start < v.end) {
+                panic!("Invalid vesting schedule");
+            }
+        }
+
+        // Validation: milestone amounts must exactly cover the escrow amount
+        if let Some(ms) = &milestones {
+            let mut total: i128 = 0;
+            for m in ms.iter() {
+                total += m.amount;
+            }
+            if total != amount {
+                panic!("Milestone amounts must sum to the escrow amount");
+            }
+        }
+
+        // Validation: vesting, milestones, a payment plan, a cross-chain
+        // destination, and a staking pool are mutually exclusive settlement
+        // paths. Allowing more than one lets a partial payout on one path
+        // (e.g. one of several milestones) coexist with a second path that
+        // still pays out the escrow's full `amount`, overdrawing the
+        // contract's pooled balance; staking is excluded from this mix for a
+        // related reason — `claim`/`terminate`, `release_milestone`/
+        // `reclaim_milestone`/`terminate_milestones`, `apply_timestamp`/
+        // `apply_signature`, and `claim_order`/`cancel_order` don't call
+        // `unstake_if_staked`, so staked funds under any of them would be
+        // unreachable at payout time.
+        let specialized_paths = [
+            vesting.is_some(),
+            milestones.is_some(),
+            plan.is_some(),
+            destination.is_some(),
+            staking_contract.is_some(),
+        ];
+        if specialized_paths.iter().filter(|configured| **configured).count() > 1 {
+            panic!("Escrow may configure at most one of vesting, milestones, plan, destination, or staking_contract");
+        }
+
+        // Generate unique engagement ID
+        let next_id = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextId)
+            .unwrap_or(1u64);
+
+        let engagement_id = next_id;
+
+        // Update next ID for future engagements
+        let next_id_key = DataKey::NextId;
+        env.storage().persistent().set(&next_id_key, &(next_id + 1));
+        env.storage()
+            .persistent()
+            .extend_ttl(&next_id_key, TTL_THRESHOLD, NEXT_ID_TTL);
+
+        // Create new escrow record
+        let escrow = Escrow {
+            client: client.clone(),
+            artisan: artisan.clone(),
+            token: token.clone(),
+            amount,
+            status: Status::Pending,
+            deadline,
+            vesting,
+            released_so_far: 0,
+            arbiter,
+            destination,
+            staking_contract,
+            staked: false,
+            yield_beneficiary,
+        };
+
+        // Store the escrow in persistent storage
+        let escrow_key = DataKey::Escrow(engagement_id);
+        env.storage().persistent().set(&escrow_key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&escrow_key, TTL_THRESHOLD, ESCROW_TTL);
+
+        // Record the optional reputation contract to hook into on release
+        if let Some(reputation_contract) = reputation_contract {
+            let reputation_key = DataKey::ReputationContract(engagement_id);
+            env.storage()
+                .persistent()
+                .set(&reputation_key, &reputation_contract);
+            env.storage()
+                .persistent()
+                .extend_ttl(&reputation_key, TTL_THRESHOLD, ESCROW_TTL);
+        }
+
+        // Record the optional conditional payment plan, settled out-of-band
+        // via `apply_timestamp`/`apply_signature`
+        if let Some(plan) = plan {
+            let plan_key = DataKey::Plan(engagement_id);
+            env.storage().persistent().set(&plan_key, &plan);
+            env.storage()
+                .persistent()
+                .extend_ttl(&plan_key, TTL_THRESHOLD, ESCROW_TTL);
+        }
+
+        // Record the optional milestone schedule, settled piecewise by
+        // `release_milestone`/`reclaim_milestone`
+        if let Some(milestones) = milestones {
+            let milestones_key = DataKey::Milestones(engagement_id);
+            env.storage().persistent().set(&milestones_key, &milestones);
+            env.storage()
+                .persistent()
+                .extend_ttl(&milestones_key, TTL_THRESHOLD, ESCROW_TTL);
+        }
+
+        // Emit event
+        env.events().publish(
+            (),
+            EngagementInitializedEvent {
+                id: engagement_id,
+                client,
+                artisan,
+            },
+        );
+
+        mmr_append(&env, "initialize", engagement_id, amount);
+
+        engagement_id
+    }
+
+    /// Deposit funds into escrow for a specific engagement.
+    ///
+    /// Any number of funders may call this repeatedly; each contribution from
+    /// `from` is tracked individually under `DataKey::Funders`, and the escrow
+    /// only transitions `Pending` -> `Funded` once the accumulated total reaches
+    /// `amount`. `from` must authorize the transfer.
+    ///
+    /// Capped to the remaining shortfall, mirroring `fund`'s logic: once the
+    /// tracked total already meets `amount` this panics instead of accepting
+    /// more, and a single deposit larger than what's still owed only pulls
+    /// the shortfall rather than overfunding the escrow, since `release`
+    /// only ever pays out `escrow.amount` and any excess would otherwise be
+    /// stranded in the contract with no recovery path.
+    pub fn deposit(env: Env, engagement_id: u64, from: Address, amount: i128) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("Deposit amount must be greater than zero");
+        }
+
+        // Load the escrow record
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Escrow not found for engagement {}", engagement_id));
+
+        // Deadline enforcement: cannot deposit after the deadline has passed
+        let current_time = env.ledger().timestamp();
+        if current_time > escrow.deadline {
+            panic!("Deadline has passed; cannot deposit into this escrow");
+        }
+
+        // Verify that the escrow is in Pending status
+        if escrow.status != Status::Pending {
+            panic!("Escrow must be in Pending status to deposit funds");
+        }
+
+        // Track this funder's contribution, capping the amount actually
+        // pulled to the remaining shortfall so `total` can never exceed
+        // `escrow.amount`.
+        let funders_key = DataKey::Funders(engagement_id);
+        let mut funders: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&funders_key)
+            .unwrap_or_else(|| Map::new(&env));
+        let already_contributed: i128 = funders.values().iter().sum();
+        let shortfall = escrow.amount - already_contributed;
+        if shortfall <= 0 {
+            panic!("Escrow is already fully funded");
+        }
+        let amount = if amount > shortfall { shortfall } else { amount };
+
+        // Transfer tokens from the funder to the escrow contract, always
+        // using the asset captured at `initialize`
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer(&from, &env.current_contract_address(), &amount);
+
+        let contributed = funders.get(from.clone()).unwrap_or(0) + amount;
+        funders.set(from, contributed);
+
+        let total: i128 = funders.values().iter().sum();
+
+        env.storage().persistent().set(&funders_key, &funders);
+        env.storage()
+            .persistent()
+            .extend_ttl(&funders_key, TTL_THRESHOLD, ESCROW_TTL);
+
+        // Transition to Funded once the accumulated deposits reach the target
+        if total >= escrow.amount {
+            set_status(&env, engagement_id, &mut escrow, Status::Funded);
+            stake_if_configured(&env, &mut escrow);
+        }
+
+        // Save the updated escrow and extend TTL
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD as u32, ESCROW_TTL as u32);
+
+        mmr_append(&env, "deposit", engagement_id, amount);
+    }
+
+    /// Fund a `Pending` escrow in one shot by pulling the remaining shortfall
+    /// from the client via a pre-approved token allowance, rather than the
+    /// direct-auth push `deposit` uses. A convenience for the common
+    /// single-funder case; crowdfunded or partial funding should use
+    /// `deposit` instead. If earlier `deposit` calls already covered some of
+    /// `amount`, only the remainder is pulled here, so those funders' tracked
+    /// contributions aren't overwritten or stranded; if they already cover
+    /// the full amount this panics rather than double-charging the client.
+    /// Requires the client to have called the token contract's `approve` for
+    /// at least the shortfall beforehand.
+    pub fn fund(env: Env, engagement_id: u64) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Escrow not found for engagement {}", engagement_id));
+
+        escrow.client.require_auth();
+
+        // Deadline enforcement: cannot fund after the deadline has passed,
+        // matching `deposit`'s own check.
+        let current_time = env.ledger().timestamp();
+        if current_time > escrow.deadline {
+            panic!("Deadline has passed; cannot fund this escrow");
+        }
+
+        if escrow.status != Status::Pending {
+            panic!("Escrow must be in Pending status to fund");
+        }
+
+        // Account for contributions already tracked via `deposit` so this
+        // only pulls the remaining shortfall instead of overwriting
+        // `funders[client]` and stranding earlier funders' money.
+        let funders_key = DataKey::Funders(engagement_id);
+        let mut funders: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&funders_key)
+            .unwrap_or_else(|| Map::new(&env));
+        let already_contributed: i128 = funders.values().iter().sum();
+        let shortfall = escrow.amount - already_contributed;
+        if shortfall <= 0 {
+            panic!("Escrow is already fully funded via deposit");
+        }
+
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer_from(
+            &contract_address,
+            &escrow.client,
+            &contract_address,
+            &shortfall,
+        );
+
+        let client_contributed = funders.get(escrow.client.clone()).unwrap_or(0) + shortfall;
+        funders.set(escrow.client.clone(), client_contributed);
+        env.storage().persistent().set(&funders_key, &funders);
+        env.storage()
+            .persistent()
+            .extend_ttl(&funders_key, TTL_THRESHOLD, ESCROW_TTL);
+
+        set_status(&env, engagement_id, &mut escrow, Status::Funded);
+        stake_if_configured(&env, &mut escrow);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), FundedEvent {
+            id: engagement_id,
+            client: escrow.client.clone(),
+            amount: escrow.amount,
+        });
+
+        mmr_append(&env, "fund", engagement_id, shortfall);
+    }
+
+    /// Release funds from escrow to the artisan
+    /// Can only be called by the client and only when escrow is funded.
+    /// Also verifies that the deadline has not passed; after the deadline the client
+    /// must use `reclaim` to retrieve funds instead.
+    ///
+    /// Rejects engagements configured for one of the specialized release
+    /// paths documented on `initialize` (vesting, milestones, a payment
+    /// plan, or a cross-chain destination) — each of those settles through
+    /// its own entrypoint (`claim`/`terminate`, `release_milestone`,
+    /// `apply_timestamp`/`apply_signature`, `release_cross_chain`) instead,
+    /// and this single all-or-nothing transfer would otherwise bypass
+    /// whatever schedule or condition they were set up to enforce.
+    pub fn release(env: Env, engagement_id: u64) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        // Auth: Require the client's signature
+        escrow.client.require_auth();
+
+        assert_no_specialized_release_path(&env, engagement_id, &escrow);
+
+        // Deadline check: prevent releasing funds after deadline has passed
+        let current_time = env.ledger().timestamp();
+        if current_time > escrow.deadline {
+            panic!("Deadline has passed; cannot release funds");
+        }
+
+        // A disputed escrow can only be settled by the arbiter.
+        if escrow.status == Status::Disputed {
+            panic!("Escrow is disputed; only the arbiter may settle it");
+        }
+
+        // Checks: Funded -> Released must be a legal transition per the
+        // shared table, which in practice means the escrow is Funded.
+        assert_transition(&env, &escrow.status, &Status::Released)
+            .expect("Escrow is not funded");
+
+        // Pull staked principal (and any yield) back into the contract before
+        // settling, if this escrow's funds were deposited with a staking pool.
+        unstake_if_staked(&env, &mut escrow);
+
+        // Logic: Transfer the stored escrow amount to the artisan, skimming
+        // the protocol fee (if configured) to the treasury first.
+        let token_client = token::Client::new(&env, &escrow.token);
+        apply_fee(&env, &token_client, escrow.amount, &escrow.artisan);
+
+        // State: Update the escrow status to Released
+        set_status(&env, engagement_id, &mut escrow, Status::Released);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD as u32, ESCROW_TTL as u32);
+
+        mmr_append(&env, "release", engagement_id, escrow.amount);
+        notify_reputation_contract(&env, engagement_id, &escrow.artisan);
+    }
+
+    /// Allow the client to reclaim funds after the deadline has passed when an escrow is still funded.
+    ///
+    /// Repays each funder their tracked contribution (or the full amount to the client if no
+    /// contributions are tracked), updates the status to `Refunded`, and emits a
+    /// [`ReclaimedEvent`]. Returns `true` on success.
+    ///
+    /// Rejects engagements configured for one of the specialized release
+    /// paths documented on `initialize` (vesting, milestones, a payment
+    /// plan, or a cross-chain destination), for the same reason `release`
+    /// does: this plain all-or-nothing refund would otherwise bypass
+    /// whatever schedule or condition they were set up to enforce. Use
+    /// `terminate`, `reclaim_milestone`/`terminate_milestones`,
+    /// `apply_timestamp`/`apply_signature`, or `cancel_order` instead.
+    pub fn reclaim(env: Env, engagement_id: u64) -> bool {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        // Auth: only the client may reclaim
+        escrow.client.require_auth();
+
+        assert_no_specialized_release_path(&env, engagement_id, &escrow);
+
+        // A disputed escrow can only be settled by the arbiter.
+        if escrow.status == Status::Disputed {
+            panic!("Escrow is disputed; only the arbiter may settle it");
+        }
+
+        // State check: Funded -> Refunded must be a legal transition per the
+        // shared table, which in practice means the escrow is Funded.
+        assert_transition(&env, &escrow.status, &Status::Refunded)
+            .expect("Escrow must be Funded to reclaim");
+
+        // Deadline check: ensure deadline has already passed
+        let current_time = env.ledger().timestamp();
+        if current_time <= escrow.deadline {
+            panic!("Deadline has not passed; cannot reclaim yet");
+        }
+
+        // Pull staked principal (and any yield) back into the contract before
+        // refunding, if this escrow's funds were deposited with a staking pool.
+        unstake_if_staked(&env, &mut escrow);
+
+        // Repay each funder their own tracked contribution. `deposit` and
+        // `fund` always record the funder, so the map is only empty if the
+        // escrow was marked Funded without either of them; fall back to the
+        // client.
+        let funders_key = DataKey::Funders(engagement_id);
+        let funders: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&funders_key)
+            .unwrap_or_else(|| Map::new(&env));
+
+        let token_client = token::Client::new(&env, &escrow.token);
+        let mut total_refunded: i128 = 0;
+        if funders.is_empty() {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &escrow.client,
+                &escrow.amount,
+            );
+            total_refunded = escrow.amount;
+        } else {
+            for (funder, contributed) in funders.iter() {
+                token_client.transfer(&env.current_contract_address(), &funder, &contributed);
+                total_refunded += contributed;
+            }
+        }
+        env.storage().persistent().remove(&funders_key);
+
+        // Update state to Refunded
+        set_status(&env, engagement_id, &mut escrow, Status::Refunded);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD as u32, ESCROW_TTL as u32);
+
+        // Emit event
+        env.events().publish(
+            (),
+            ReclaimedEvent {
+                id: engagement_id,
+                client: escrow.client.clone(),
+                amount: total_refunded,
+                timestamp: current_time,
+            },
+        );
+
+        mmr_append(&env, "refund", engagement_id, total_refunded);
+
+        true
+    }
+
+    /// Claim the currently-vested, not-yet-released portion of a vesting escrow.
+    ///
+    /// Computes `vested = 0` before the cliff, otherwise
+    /// `min(amount, amount * (now - start) / (end - start))`, and transfers
+    /// `vested - released_so_far` to the artisan. Once `released_so_far == amount`
+    /// the escrow transitions to `Released`.
+    pub fn claim(env: Env, engagement_id: u64) -> i128 {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        let vesting = escrow
+            .vesting
+            .clone()
+            .unwrap_or_else(|| panic!("Escrow has no vesting schedule"));
+
+        if escrow.status != Status::Funded && escrow.status != Status::Vesting {
+            panic!("Escrow must be Funded or Vesting to claim");
+        }
+
+        let now = env.ledger().timestamp();
+        let vested = Self::vested_amount(&vesting, escrow.amount, now);
+        let payable = vested - escrow.released_so_far;
+        if payable <= 0 {
+            panic!("Nothing new has vested yet");
+        }
+
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &escrow.artisan,
+            &payable,
+        );
+
+        escrow.released_so_far = vested;
+        let next_status = if escrow.released_so_far == escrow.amount {
+            Status::Released
+        } else {
+            Status::Vesting
+        };
+        set_status(&env, engagement_id, &mut escrow, next_status);
+
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish(
+            (),
+            ClaimedEvent {
+                id: engagement_id,
+                artisan: escrow.artisan,
+                amount: payable,
+                released_so_far: escrow.released_so_far,
+            },
+        );
+
+        mmr_append(&env, "claim", engagement_id, payable);
+
+        payable
+    }
+
+    /// Let the client terminate a vesting escrow early: the artisan is paid out
+    /// whatever has vested so far (if not already claimed), and the unvested
+    /// remainder is refunded to the client.
+    pub fn terminate(env: Env, engagement_id: u64) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        escrow.client.require_auth();
+
+        let vesting = escrow
+            .vesting
+            .clone()
+            .unwrap_or_else(|| panic!("Escrow has no vesting schedule"));
+
+        if escrow.status != Status::Funded && escrow.status != Status::Vesting {
+            panic!("Escrow must be Funded or Vesting to terminate");
+        }
+
+        let now = env.ledger().timestamp();
+        let vested = Self::vested_amount(&vesting, escrow.amount, now);
+        let owed_to_artisan = vested - escrow.released_so_far;
+        let refund_to_client = escrow.amount - vested;
+
+        let token_client = token::Client::new(&env, &escrow.token);
+        if owed_to_artisan > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &escrow.artisan,
+                &owed_to_artisan,
+            );
+        }
+        if refund_to_client > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &escrow.client,
+                &refund_to_client,
+            );
+        }
+
+        escrow.released_so_far = vested;
+        set_status(&env, engagement_id, &mut escrow, Status::Refunded);
+
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish(
+            (),
+            TerminatedEvent {
+                id: engagement_id,
+                client: escrow.client,
+                vested_to_artisan: owed_to_artisan,
+                refunded_to_client: refund_to_client,
+            },
+        );
+
+        mmr_append(&env, "terminate", engagement_id, owed_to_artisan + refund_to_client);
+    }
+
+    /// Release a single milestone's funds to the artisan. Requires the
+    /// client's auth; the escrow stays `Funded` until every milestone has
+    /// been settled, at which point it becomes `Released`.
+    pub fn release_milestone(env: Env, engagement_id: u64, milestone_index: u32) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        escrow.client.require_auth();
+
+        assert_transition(&env, &escrow.status, &Status::Released)
+            .expect("Escrow must be Funded to release a milestone");
+
+        let milestones_key = DataKey::Milestones(engagement_id);
+        let mut milestones: Vec<Milestone> = env
+            .storage()
+            .persistent()
+            .get(&milestones_key)
+            .unwrap_or_else(|| panic!("No milestone schedule configured for this engagement"));
+
+        let mut milestone = milestones
+            .get(milestone_index)
+            .unwrap_or_else(|| panic!("Milestone index out of range"));
+        if milestone.released {
+            panic!("Milestone has already been settled");
+        }
+
+        let token_client = token::Client::new(&env, &escrow.token);
+        apply_fee(&env, &token_client, milestone.amount, &escrow.artisan);
+
+        milestone.released = true;
+        milestones.set(milestone_index, milestone.clone());
+        env.storage().persistent().set(&milestones_key, &milestones);
+        env.storage()
+            .persistent()
+            .extend_ttl(&milestones_key, TTL_THRESHOLD, ESCROW_TTL);
+
+        let all_settled = milestones.iter().all(|m| m.released);
+        if all_settled {
+            set_status(&env, engagement_id, &mut escrow, Status::Released);
+            env.storage().persistent().set(&key, &escrow);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+        }
+
+        env.events().publish((), MilestoneReleasedEvent {
+            id: engagement_id,
+            index: milestone_index,
+            amount: milestone.amount,
+        });
+
+        mmr_append(&env, "release_milestone", engagement_id, milestone.amount);
+        if all_settled {
+            notify_reputation_contract(&env, engagement_id, &escrow.artisan);
+        }
+    }
+
+    /// Reclaim a single milestone's funds back to the client, once that
+    /// milestone's own deadline has passed without it being released.
+    pub fn reclaim_milestone(env: Env, engagement_id: u64, milestone_index: u32) {
+        let key = DataKey::Escrow(engagement_id);
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        escrow.client.require_auth();
+
+        if escrow.status != Status::Funded {
+            panic!("Escrow must be Funded to reclaim a milestone");
+        }
+
+        let milestones_key = DataKey::Milestones(engagement_id);
+        let mut milestones: Vec<Milestone> = env
+            .storage()
+            .persistent()
+            .get(&milestones_key)
+            .unwrap_or_else(|| panic!("No milestone schedule configured for this engagement"));
+
+        let mut milestone = milestones
+            .get(milestone_index)
+            .unwrap_or_else(|| panic!("Milestone index out of range"));
+        if milestone.released {
+            panic!("Milestone has already been settled");
+        }
+
+        if env.ledger().timestamp() <= milestone.deadline {
+            panic!("Milestone deadline has not passed");
+        }
+
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer(&env.current_contract_address(), &escrow.client, &milestone.amount);
+
+        milestone.released = true;
+        milestones.set(milestone_index, milestone.clone());
+        env.storage().persistent().set(&milestones_key, &milestones);
+        env.storage()
+            .persistent()
+            .extend_ttl(&milestones_key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), MilestoneReclaimedEvent {
+            id: engagement_id,
+            index: milestone_index,
+            amount: milestone.amount,
+        });
+
+        mmr_append(&env, "reclaim_milestone", engagement_id, milestone.amount);
+    }
+
+    /// Let the client end a milestone-based escrow early, analogous to
+    /// `terminate` for vesting escrows: every milestone not yet released is
+    /// refunded to the client in one call, already-released milestones are
+    /// left untouched, and the escrow moves to `Refunded`.
+    pub fn terminate_milestones(env: Env, engagement_id: u64) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        escrow.client.require_auth();
+
+        assert_transition(&env, &escrow.status, &Status::Refunded)
+            .expect("Escrow must be Funded to terminate its milestones");
+
+        let milestones_key = DataKey::Milestones(engagement_id);
+        let mut milestones: Vec<Milestone> = env
+            .storage()
+            .persistent()
+            .get(&milestones_key)
+            .unwrap_or_else(|| panic!("No milestone schedule configured for this engagement"));
+
+        let mut refund_to_client: i128 = 0;
+        for i in 0..milestones.len() {
+            let mut milestone = milestones.get(i).unwrap();
+            if !milestone.released {
+                refund_to_client += milestone.amount;
+                milestone.released = true;
+                milestones.set(i, milestone);
+            }
+        }
+        env.storage().persistent().set(&milestones_key, &milestones);
+        env.storage()
+            .persistent()
+            .extend_ttl(&milestones_key, TTL_THRESHOLD, ESCROW_TTL);
+
+        if refund_to_client > 0 {
+            let token_client = token::Client::new(&env, &escrow.token);
+            token_client.transfer(
+                &env.current_contract_address(),
+                &escrow.client,
+                &refund_to_client,
+            );
+        }
+
+        set_status(&env, engagement_id, &mut escrow, Status::Refunded);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), MilestonesTerminatedEvent {
+            id: engagement_id,
+            client: escrow.client,
+            refunded_to_client: refund_to_client,
+        });
+
+        mmr_append(&env, "terminate_milestones", engagement_id, refund_to_client);
+    }
+
+    /// Open a dispute on a funded escrow. Callable by either the client or the
+    /// artisan; moves the escrow from `Funded` to `Disputed`, after which only
+    /// the arbiter's `resolve` call can move funds.
+    ///
+    /// Rejects engagements configured for one of the specialized release
+    /// paths (vesting, milestones, a payment plan, or a cross-chain
+    /// destination), for the same reason `release`/`reclaim` do: `resolve`/
+    /// `resolve_dispute`/`resolve_by_bps` always settle the full
+    /// `escrow.amount`, which would double-pay whatever those paths have
+    /// already released via `claim`/`release_milestone`/etc.
+    pub fn dispute(env: Env, engagement_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        if caller != escrow.client && caller != escrow.artisan {
+            panic!("Only the client or artisan may open a dispute");
+        }
+
+        assert_transition(&env, &escrow.status, &Status::Disputed)
+            .expect("Escrow must be Funded to dispute");
+
+        assert_no_specialized_release_path(&env, engagement_id, &escrow);
+
+        set_status(&env, engagement_id, &mut escrow, Status::Disputed);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), DisputedEvent {
+            id: engagement_id,
+            opened_by: caller,
+        });
+
+        mmr_append(&env, "dispute", engagement_id, escrow.amount);
+    }
+
+    /// Resolve a disputed escrow. Callable only by the configured arbiter, who
+    /// decides whether the full amount goes to the artisan or back to the client.
+    pub fn resolve(env: Env, engagement_id: u64, to_artisan: bool) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        let arbiter = escrow
+            .arbiter
+            .clone()
+            .unwrap_or_else(|| panic!("Escrow has no arbiter configured"));
+        arbiter.require_auth();
+
+        let next_status = if to_artisan { Status::Released } else { Status::Refunded };
+        assert_transition(&env, &escrow.status, &next_status)
+            .expect("Escrow must be Disputed to resolve");
+
+        // Defense in depth: `dispute` already refuses to move an escrow with
+        // a specialized release path to `Disputed`, but this transfers the
+        // full `escrow.amount` regardless of `released_so_far`, so re-check
+        // here too rather than relying solely on that earlier gate.
+        assert_no_specialized_release_path(&env, engagement_id, &escrow);
+
+        // Pull staked principal (and any yield) back into the contract before
+        // settling, if this escrow's funds were deposited with a staking
+        // pool: a disputed escrow can still be staked, since `dispute` itself
+        // doesn't unstake.
+        unstake_if_staked(&env, &mut escrow);
+
+        // Dispute resolution is fee-exempt regardless of which party the
+        // arbiter favors: unlike a plain `release`, this path only runs when
+        // something has already gone wrong between client and artisan.
+        let token_client = token::Client::new(&env, &escrow.token);
+        let recipient = if to_artisan { &escrow.artisan } else { &escrow.client };
+        token_client.transfer(&env.current_contract_address(), recipient, &escrow.amount);
+
+        set_status(&env, engagement_id, &mut escrow, next_status);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), ResolvedEvent {
+            id: engagement_id,
+            arbiter,
+            to_artisan,
+            amount: escrow.amount,
+        });
+
+        mmr_append(&env, "resolve", engagement_id, escrow.amount);
+        if to_artisan {
+            notify_reputation_contract(&env, engagement_id, &escrow.artisan);
+        }
+    }
+
+    /// Resolve a disputed escrow with an arbitrary split between both
+    /// parties, for arbiters who want to apportion partial fault rather than
+    /// award the full amount to one side as the boolean `resolve` does.
+    /// `to_artisan + to_client` must equal the escrow amount exactly.
+    pub fn resolve_dispute(
+        env: Env,
+        engagement_id: u64,
+        to_artisan: i128,
+        to_client: i128,
+    ) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        let arbiter = escrow
+            .arbiter
+            .clone()
+            .unwrap_or_else(|| panic!("Escrow has no arbiter configured"));
+        arbiter.require_auth();
+
+        // `to_artisan == 0` settles as a full refund to the client, same as
+        // the boolean `resolve(false)`; any other non-negative split that
+        // sums to the escrow amount settles as `Released`.
+        let next_status = if to_artisan > 0 { Status::Released } else { Status::Refunded };
+        assert_transition(&env, &escrow.status, &next_status)
+            .expect("Escrow must be Disputed to resolve");
+
+        // Defense in depth; see the matching check in `resolve`.
+        assert_no_specialized_release_path(&env, engagement_id, &escrow);
+
+        if to_artisan < 0 || to_client < 0 || to_artisan + to_client != escrow.amount {
+            panic!("to_artisan and to_client must be non-negative and sum to the escrow amount");
+        }
+
+        // Pull staked principal (and any yield) back into the contract
+        // before settling; see the matching call in `resolve`.
+        unstake_if_staked(&env, &mut escrow);
+
+        // Dispute resolution is fee-exempt, same as the boolean `resolve`.
+        let token_client = token::Client::new(&env, &escrow.token);
+        let contract_address = env.current_contract_address();
+        if to_artisan > 0 {
+            token_client.transfer(&contract_address, &escrow.artisan, &to_artisan);
+        }
+        if to_client > 0 {
+            token_client.transfer(&contract_address, &escrow.client, &to_client);
+        }
+
+        set_status(&env, engagement_id, &mut escrow, next_status);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), DisputeResolvedEvent {
+            id: engagement_id,
+            arbiter,
+            to_artisan,
+            to_client,
+        });
+
+        mmr_append(&env, "resolve_dispute", engagement_id, escrow.amount);
+        if to_artisan > 0 {
+            notify_reputation_contract(&env, engagement_id, &escrow.artisan);
+        }
+    }
+
+    /// Resolve a disputed escrow by basis-points split, for arbiters who
+    /// think in percentages rather than explicit amounts. `artisan_bps` is
+    /// out of 10_000; the remainder goes to the client. Equivalent to
+    /// computing the amounts and calling `resolve_dispute`.
+    pub fn resolve_by_bps(env: Env, engagement_id: u64, artisan_bps: u32) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Escrow not found");
+
+        let arbiter = escrow
+            .arbiter
+            .clone()
+            .unwrap_or_else(|| panic!("Escrow has no arbiter configured"));
+        arbiter.require_auth();
+
+        // Defense in depth; see the matching check in `resolve`.
+        assert_no_specialized_release_path(&env, engagement_id, &escrow);
+
+        // Checked ahead of computing `to_artisan` below so an out-of-range
+        // `artisan_bps` is rejected before it's multiplied into the split.
+        if artisan_bps > 10_000 {
+            panic!("artisan_bps cannot exceed 10000");
+        }
 
-#[contractimpl]
-impl EscrowContract {
-    /// Initialize a new escrow engagement
-    /// Creates a new escrow record with Pending status
-    pub fn initialize(
-        env: Env,
-        client: Address,
-        artisan: Address,
-        amount: i128,
-        deadline: u64,
-    ) -> u64 {
-        // Validation: client cannot be the same as artisan
-        if client == artisan {
-            panic!("Client and artisan cannot be the same address");
+        let to_artisan = escrow.amount * artisan_bps as i128 / 10_000;
+        let to_client = escrow.amount - to_artisan;
+
+        // `artisan_bps == 0` settles as a full refund to the client, same as
+        // `resolve_dispute(id, 0, amount)`; any other split settles as
+        // `Released`.
+        let next_status = if to_artisan > 0 { Status::Released } else { Status::Refunded };
+        assert_transition(&env, &escrow.status, &next_status)
+            .expect("Escrow must be Disputed to resolve");
+
+        // Pull staked principal (and any yield) back into the contract
+        // before settling; see the matching call in `resolve`.
+        unstake_if_staked(&env, &mut escrow);
+
+        // Dispute resolution is fee-exempt, same as `resolve`/`resolve_dispute`.
+        let token_client = token::Client::new(&env, &escrow.token);
+        let contract_address = env.current_contract_address();
+        if to_artisan > 0 {
+            token_client.transfer(&contract_address, &escrow.artisan, &to_artisan);
+        }
+        if to_client > 0 {
+            token_client.transfer(&contract_address, &escrow.client, &to_client);
         }
 
-        // Validation: amount must be positive
-        if amount <= 0 {
-            panic!("Amount must be greater than zero");
+        set_status(&env, engagement_id, &mut escrow, next_status);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), DisputeResolvedEvent {
+            id: engagement_id,
+            arbiter,
+            to_artisan,
+            to_client,
+        });
+
+        mmr_append(&env, "resolve_by_bps", engagement_id, escrow.amount);
+        if to_artisan > 0 {
+            notify_reputation_contract(&env, engagement_id, &escrow.artisan);
         }
+    }
 
-        // Generate unique engagement ID
-        let next_id = env
+    /// Read an engagement's current status.
+    pub fn get_escrow_status(env: Env, engagement_id: u64) -> Status {
+        let escrow: Escrow = env
             .storage()
             .persistent()
-            .get(&DataKey::NextId)
-            .unwrap_or(1u64);
+            .get(&DataKey::Escrow(engagement_id))
+            .expect("Escrow not found");
+        escrow.status
+    }
 
-        let engagement_id = next_id;
+    /// List the statuses an engagement could legally move to next, per the
+    /// transition table in `allowed_next_states` (the free function). Empty
+    /// for `Released`/`Refunded`, which are terminal.
+    pub fn allowed_next_states(env: Env, engagement_id: u64) -> Vec<Status> {
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(engagement_id))
+            .expect("Escrow not found");
+        allowed_next_states(&env, &escrow.status)
+    }
 
-        // Update next ID for future engagements
-        let next_id_key = DataKey::NextId;
-        env.storage().persistent().set(&next_id_key, &(next_id + 1));
-        env.storage()
+    /// Settle a funded escrow configured with a `destination` to a bridged
+    /// chain instead of paying the artisan directly on Stellar. Locks the
+    /// escrow amount under `DataKey::PendingOrder(order_hash)` and emits a
+    /// `CrossChainOrderEvent` for the off-chain relayer watching
+    /// `destination.chain_id` to pick up and fulfill, then settle via
+    /// `claim_order`. `order_expiry` bounds how long the relayer has before
+    /// the client may `cancel_order` and recover the funds.
+    ///
+    /// Moves the escrow to `CrossChainPending`, not `Released` — the payout
+    /// isn't confirmed until the relayer actually claims the order, so
+    /// `rate()` (gated on `Released`) can't be called, and the artisan isn't
+    /// notified on the reputation contract, until then.
+    pub fn release_cross_chain(env: Env, engagement_id: u64, order_expiry: u64) {
+        let key = DataKey::Escrow(engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
             .persistent()
-            .extend_ttl(&next_id_key, TTL_THRESHOLD, NEXT_ID_TTL);
+            .get(&key)
+            .expect("Escrow not found");
 
-        // Create new escrow record
-        let escrow = Escrow {
-            client: client.clone(),
-            artisan: artisan.clone(),
-            amount,
-            status: Status::Pending,
-            deadline,
+        escrow.client.require_auth();
+
+        assert_transition(&env, &escrow.status, &Status::CrossChainPending)
+            .expect("Escrow must be Funded to release cross-chain");
+
+        let destination = escrow
+            .destination
+            .clone()
+            .unwrap_or_else(|| panic!("Escrow has no cross-chain destination configured"));
+
+        if order_expiry <= env.ledger().timestamp() {
+            panic!("order_expiry must be in the future");
+        }
+
+        let order_key = DataKey::PendingOrder(destination.order_hash.clone());
+        let order = PendingOrder {
+            engagement_id,
+            client: escrow.client.clone(),
+            token: escrow.token.clone(),
+            amount: escrow.amount,
+            expiry: order_expiry,
         };
+        env.storage().persistent().set(&order_key, &order);
+        env.storage()
+            .persistent()
+            .extend_ttl(&order_key, TTL_THRESHOLD, ESCROW_TTL);
 
-        // Store the escrow in persistent storage
-        let escrow_key = DataKey::Escrow(engagement_id);
+        set_status(&env, engagement_id, &mut escrow, Status::CrossChainPending);
+        env.storage().persistent().set(&key, &escrow);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), CrossChainOrderEvent {
+            id: engagement_id,
+            chain_id: destination.chain_id,
+            recipient: destination.recipient,
+            order_hash: destination.order_hash,
+            amount: escrow.amount,
+        });
+
+        mmr_append(&env, "release_cross_chain", engagement_id, escrow.amount);
+    }
+
+    /// Pay out a pending cross-chain order once the registered relayer
+    /// proves fulfillment. For now that proof is simply the registered
+    /// relayer's own `require_auth`, and funds settle to that same
+    /// relayer-supplied Stellar address. Returns the amount paid.
+    ///
+    /// This is the confirmation point for `release_cross_chain`: only now
+    /// does the escrow move `CrossChainPending -> Released` and notify the
+    /// reputation contract, matching every other release path.
+    pub fn claim_order(env: Env, order_hash: BytesN<32>, relayer: Address) -> i128 {
+        let registered_relayer: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Relayer)
+            .unwrap_or_else(|| panic!("No relayer is registered"));
+        if relayer != registered_relayer {
+            panic!("Only the registered relayer may claim an order");
+        }
+        relayer.require_auth();
+
+        let order_key = DataKey::PendingOrder(order_hash.clone());
+        let order: PendingOrder = env
+            .storage()
+            .persistent()
+            .get(&order_key)
+            .unwrap_or_else(|| panic!("No pending order for this hash"));
+
+        let token_client = token::Client::new(&env, &order.token);
+        token_client.transfer(&env.current_contract_address(), &relayer, &order.amount);
+
+        env.storage().persistent().remove(&order_key);
+
+        let escrow_key = DataKey::Escrow(order.engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&escrow_key)
+            .expect("Escrow not found");
+        // Defense in depth: a `PendingOrder` only exists while its escrow is
+        // `CrossChainPending`, but re-check against the shared table rather
+        // than relying solely on that invariant.
+        assert_transition(&env, &escrow.status, &Status::Released)
+            .expect("Escrow must be CrossChainPending to claim its order");
+        set_status(&env, order.engagement_id, &mut escrow, Status::Released);
         env.storage().persistent().set(&escrow_key, &escrow);
         env.storage()
             .persistent()
             .extend_ttl(&escrow_key, TTL_THRESHOLD, ESCROW_TTL);
 
-        // Emit event
-        env.events().publish(
-            (),
-            EngagementInitializedEvent {
-                id: engagement_id,
-                client,
-                artisan,
-            },
-        );
+        env.events().publish((), OrderClaimedEvent {
+            order_hash,
+            relayer,
+            amount: order.amount,
+        });
 
-        engagement_id
+        mmr_append(&env, "claim_order", order.engagement_id, order.amount);
+        notify_reputation_contract(&env, order.engagement_id, &escrow.artisan);
+        order.amount
     }
 
-    /// Deposit funds into escrow for a specific engagement
-    /// The client must have previously authorized the escrow contract to spend tokens
-    pub fn deposit(env: Env, engagement_id: u64, token: Address) {
-        // Load the escrow record
-        let key = DataKey::Escrow(engagement_id);
-        let mut escrow: Escrow = env
+    /// Return a pending cross-chain order's funds to the client once its
+    /// `order_expiry` has passed without the relayer claiming it.
+    pub fn cancel_order(env: Env, order_hash: BytesN<32>) {
+        let order_key = DataKey::PendingOrder(order_hash.clone());
+        let order: PendingOrder = env
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic!("Escrow not found for engagement {}", engagement_id));
-
-        // Deadline enforcement: cannot deposit after the deadline has passed
-        let current_time = env.ledger().timestamp();
-        if current_time > escrow.deadline {
-            panic!("Deadline has passed; cannot deposit into this escrow");
-        }
+            .get(&order_key)
+            .unwrap_or_else(|| panic!("No pending order for this hash"));
 
-        // Note: Authorization should be verified by the calling application
-        // In a production system, this would require client signature verification
+        order.client.require_auth();
 
-        // Verify that the escrow is in Pending status
-        if escrow.status != Status::Pending {
-            panic!("Escrow must be in Pending status to deposit funds");
+        if env.ledger().timestamp() <= order.expiry {
+            panic!("Order has not expired yet");
         }
 
-        // Transfer tokens from client to escrow contract
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(
-            &escrow.client,
-            &env.current_contract_address(),
-            &escrow.amount,
-        );
+        let token_client = token::Client::new(&env, &order.token);
+        token_client.transfer(&env.current_contract_address(), &order.client, &order.amount);
 
-        // Update escrow status to Funded
-        escrow.status = Status::Funded;
+        env.storage().persistent().remove(&order_key);
 
-        // Save the updated escrow and extend TTL
-        env.storage().persistent().set(&key, &escrow);
+        let escrow_key = DataKey::Escrow(order.engagement_id);
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&escrow_key)
+            .expect("Escrow not found");
+        // Defense in depth; see the matching check in `claim_order`.
+        assert_transition(&env, &escrow.status, &Status::Funded)
+            .expect("Escrow must be CrossChainPending to cancel its order");
+        set_status(&env, order.engagement_id, &mut escrow, Status::Funded);
+        env.storage().persistent().set(&escrow_key, &escrow);
         env.storage()
             .persistent()
-            .extend_ttl(&key, TTL_THRESHOLD as u32, ESCROW_TTL as u32);
+            .extend_ttl(&escrow_key, TTL_THRESHOLD, ESCROW_TTL);
+
+        env.events().publish((), OrderCancelledEvent {
+            order_hash,
+            amount: order.amount,
+        });
+
+        mmr_append(&env, "cancel_order", order.engagement_id, order.amount);
     }
 
-    /// Release funds from escrow to the artisan
-    /// Can only be called by the client and only when escrow is funded.
-    /// Also verifies that the deadline has not passed; after the deadline the client
-    /// must use `reclaim` to retrieve funds instead.
-    pub fn release(env: Env, engagement_id: u64, token: Address) {
+    /// Evaluate a funded escrow's conditional payment plan against the
+    /// current ledger timestamp, firing whichever branch's `Timestamp`
+    /// condition is now met. Returns `true` if a payment fired.
+    pub fn apply_timestamp(env: Env, engagement_id: u64) -> bool {
+        let now = env.ledger().timestamp();
+        try_execute_plan(&env, engagement_id, PlanTrigger::Timestamp(now))
+    }
+
+    /// Evaluate a funded escrow's conditional payment plan against a
+    /// signer's authorization, firing whichever branch's `Signature`
+    /// condition names `signer`. Returns `true` if a payment fired.
+    pub fn apply_signature(env: Env, engagement_id: u64, signer: Address) -> bool {
+        signer.require_auth();
+        try_execute_plan(&env, engagement_id, PlanTrigger::Signature(signer))
+    }
+
+    /// Let the client leave a star rating for the artisan on the configured
+    /// reputation contract once the engagement has been released.
+    pub fn rate(env: Env, engagement_id: u64, score: u32) {
         let key = DataKey::Escrow(engagement_id);
-        let mut escrow: Escrow = env
+        let escrow: Escrow = env
             .storage()
             .persistent()
             .get(&key)
             .expect("Escrow not found");
 
-        // Auth: Require the client's signature
         escrow.client.require_auth();
 
-        // Deadline check: prevent releasing funds after deadline has passed
-        let current_time = env.ledger().timestamp();
-        if current_time > escrow.deadline {
-            panic!("Deadline has passed; cannot release funds");
+        if escrow.status != Status::Released {
+            panic!("Escrow must be Released before rating the artisan");
         }
 
-        // Checks: Ensure the escrow status is Funded
-        if escrow.status != Status::Funded {
-            panic!("Escrow is not funded");
+        let reputation_key = DataKey::ReputationContract(engagement_id);
+        let contract_address: Address = env
+            .storage()
+            .persistent()
+            .get(&reputation_key)
+            .unwrap_or_else(|| panic!("No reputation contract configured for this engagement"));
+
+        let client = ReputationContractClient::new(&env, &contract_address);
+        client.rate_artisan(&escrow.artisan, &score, &escrow.client);
+    }
+
+    /// Set (or update) the protocol fee configuration. The first caller to
+    /// invoke this bootstraps themselves as the admin; every later call must
+    /// be authorized by that same admin address.
+    pub fn set_fee_config(env: Env, admin: Address, treasury: Address, bps: u32) {
+        admin.require_auth();
+
+        if bps > 10_000 {
+            panic!("Fee bps cannot exceed 10000");
         }
 
-        // Logic: Transfer the stored escrow amount from the contract address to the artisan's address
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &escrow.artisan,
-            &escrow.amount,
-        );
+        bootstrap_or_require_admin(&env, &admin, "Only the admin may update the fee configuration");
 
-        // State: Update the escrow status to Released
-        escrow.status = Status::Released;
-        env.storage().persistent().set(&key, &escrow);
+        let config = FeeConfig { treasury, bps };
+        let config_key = DataKey::FeeConfig;
+        env.storage().persistent().set(&config_key, &config);
         env.storage()
             .persistent()
-            .extend_ttl(&key, TTL_THRESHOLD as u32, ESCROW_TTL as u32);
+            .extend_ttl(&config_key, TTL_THRESHOLD, NEXT_ID_TTL);
     }
 
-    /// Allow the client to reclaim funds after the deadline has passed when an escrow is still funded.
-    ///
-    /// Transfers the amount back to the client, updates the status to `Refunded`, and emits a
-    /// [`ReclaimedEvent`]. Returns `true` on success.
-    pub fn reclaim(env: Env, engagement_id: u64, token: Address) -> bool {
-        let key = DataKey::Escrow(engagement_id);
-        let mut escrow: Escrow = env
-            .storage()
+    /// Read the current protocol fee configuration, if one has been set.
+    pub fn get_fee_config(env: Env) -> Option<FeeConfig> {
+        env.storage().persistent().get(&DataKey::FeeConfig)
+    }
+
+    /// Add `token` to the set of assets `initialize` may accept. The first
+    /// caller of this (or `set_fee_config`) bootstraps themselves as admin.
+    pub fn allow_token(env: Env, admin: Address, token: Address) {
+        admin.require_auth();
+        bootstrap_or_require_admin(&env, &admin, "Only the admin may manage the token allow-list");
+
+        let key = DataKey::AllowedToken(token);
+        env.storage().persistent().set(&key, &true);
+        env.storage()
             .persistent()
-            .get(&key)
-            .expect("Escrow not found");
+            .extend_ttl(&key, TTL_THRESHOLD, NEXT_ID_TTL);
 
-        // Auth: only the client may reclaim
-        escrow.client.require_auth();
+        let active_key = DataKey::TokenAllowlistActive;
+        env.storage().persistent().set(&active_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&active_key, TTL_THRESHOLD, NEXT_ID_TTL);
+    }
 
-        // State check: must be funded
-        if escrow.status != Status::Funded {
-            panic!("Escrow must be Funded to reclaim");
+    /// Remove `token` from the allow-list; existing engagements already
+    /// using it are unaffected, but no new one may `initialize` with it.
+    pub fn disallow_token(env: Env, admin: Address, token: Address) {
+        admin.require_auth();
+        bootstrap_or_require_admin(&env, &admin, "Only the admin may manage the token allow-list");
+        env.storage().persistent().remove(&DataKey::AllowedToken(token));
+    }
+
+    /// Whether `token` is currently on the allow-list.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AllowedToken(token))
+            .unwrap_or(false)
+    }
+
+    /// Replace the entire token allow-list in one call, for admins who manage
+    /// policy as a set rather than one `allow_token`/`disallow_token` call at
+    /// a time. Activates the allow-list (same as `allow_token`) and removes
+    /// any token of the previous `set_allowed_tokens` list not present in
+    /// `tokens`; tokens added individually via `allow_token` stay allowed.
+    pub fn set_allowed_tokens(env: Env, admin: Address, tokens: Vec<Address>) {
+        admin.require_auth();
+        bootstrap_or_require_admin(&env, &admin, "Only the admin may manage the token allow-list");
+
+        let previous: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or_else(|| Vec::new(&env));
+        for token in previous.iter() {
+            if !tokens.iter().any(|t| t == token) {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::AllowedToken(token));
+            }
         }
 
-        // Deadline check: ensure deadline has already passed
-        let current_time = env.ledger().timestamp();
-        if current_time <= escrow.deadline {
-            panic!("Deadline has not passed; cannot reclaim yet");
+        for token in tokens.iter() {
+            let key = DataKey::AllowedToken(token.clone());
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, NEXT_ID_TTL);
         }
 
-        // Transfer funds back to client
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &escrow.client,
-            &escrow.amount,
-        );
+        let tokens_key = DataKey::AllowedTokens;
+        env.storage().persistent().set(&tokens_key, &tokens);
+        env.storage()
+            .persistent()
+            .extend_ttl(&tokens_key, TTL_THRESHOLD, NEXT_ID_TTL);
 
-        // Update state to Refunded
-        escrow.status = Status::Refunded;
-        env.storage().persistent().set(&key, &escrow);
+        let active_key = DataKey::TokenAllowlistActive;
+        env.storage().persistent().set(&active_key, &true);
         env.storage()
             .persistent()
-            .extend_ttl(&key, TTL_THRESHOLD as u32, ESCROW_TTL as u32);
+            .extend_ttl(&active_key, TTL_THRESHOLD, NEXT_ID_TTL);
 
-        // Emit event
-        env.events().publish(
-            (),
-            ReclaimedEvent {
-                id: engagement_id,
-                client: escrow.client.clone(),
-                amount: escrow.amount,
-                timestamp: current_time,
-            },
-        );
+        env.events()
+            .publish((), AllowedTokensChangedEvent { tokens });
+    }
 
-        true
+    /// Read the token allow-list as last set via `set_allowed_tokens`. Tokens
+    /// added individually via `allow_token` don't appear here; use
+    /// `is_token_allowed` to check a single token regardless of how it was added.
+    pub fn get_allowed_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set (or update) the relayer trusted to `claim_order` on behalf of
+    /// cross-chain settlements. The first caller to invoke this bootstraps
+    /// themselves as the admin, same as `set_fee_config`.
+    pub fn register_relayer(env: Env, admin: Address, relayer: Address) {
+        admin.require_auth();
+        bootstrap_or_require_admin(&env, &admin, "Only the admin may register the relayer");
+
+        let key = DataKey::Relayer;
+        env.storage().persistent().set(&key, &relayer);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, NEXT_ID_TTL);
+    }
+
+    /// The current MMR root over every recorded state-transition event, i.e. the
+    /// peaks "bagged" right-to-left. An MMR with no leaves has a well-defined
+    /// all-zero root.
+    pub fn mmr_root(env: Env) -> BytesN<32> {
+        let peak_positions: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MmrPeaks)
+            .unwrap_or_else(|| Vec::new(&env));
+        if peak_positions.is_empty() {
+            return BytesN::from_array(&env, &[0u8; 32]);
+        }
+
+        let nodes: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MmrNodes)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut peaks: Vec<BytesN<32>> = Vec::new(&env);
+        for pos in peak_positions.iter() {
+            peaks.push_back(nodes.get(pos).unwrap());
+        }
+        mmr_bag_peaks(&env, &peaks)
+    }
+
+    /// Pure proof-verification helper: recompute the peak that `leaf` (at
+    /// `leaf_index`) belongs to by folding it up `merkle_path` (siblings from
+    /// the leaf to its peak, bit `i` of `leaf_index` selecting whether the
+    /// leaf-side node is hashed on the left or right at each step), confirm
+    /// that peak is present in `peaks`, then bag all of `peaks` and compare
+    /// the result to `root`.
+    pub fn verify_proof(
+        env: Env,
+        leaf: BytesN<32>,
+        leaf_index: u64,
+        merkle_path: Vec<BytesN<32>>,
+        peaks: Vec<BytesN<32>>,
+        root: BytesN<32>,
+    ) -> bool {
+        if peaks.is_empty() {
+            return false;
+        }
+
+        let mut acc = leaf;
+        let mut idx = leaf_index;
+        for sibling in merkle_path.iter() {
+            acc = if idx & 1 == 0 {
+                mmr_hash_pair(&env, &acc, &sibling)
+            } else {
+                mmr_hash_pair(&env, &sibling, &acc)
+            };
+            idx >>= 1;
+        }
+
+        if !peaks.iter().any(|p| p == acc) {
+            return false;
+        }
+
+        mmr_bag_peaks(&env, &peaks) == root
+    }
+
+    /// Compute the total amount vested as of `now`: zero before the cliff, linear
+    /// between `start` and `end`, capped at `amount`.
+    fn vested_amount(vesting: &VestingConfig, amount: i128, now: u64) -> i128 {
+        if now < vesting.cliff {
+            return 0;
+        }
+        if now >= vesting.end {
+            return amount;
+        }
+        let elapsed = (now - vesting.start) as i128;
+        let total = (vesting.end - vesting.start) as i128;
+        (amount * elapsed) / total
     }
 }
 
@@ -281,10 +2364,17 @@ mod test_legacy {
         let artisan_address = Address::generate(&env);
         let amount: i128 = 1000;
         let deadline = env.ledger().timestamp() + 86400; // 24 hours from now
+        let token_address = Address::generate(&env);
 
         // Initialize engagement
-        let engagement_id =
-            client.initialize(&client_address, &artisan_address, &amount, &deadline);
+        let engagement_id = client.initialize(
+            &client_address,
+            &artisan_address,
+            &amount,
+            &deadline,
+            &token_address,
+            &EngagementOptions::default(),
+        );
 
         // Verify the returned ID is valid (should be 1 for first engagement)
         assert_eq!(engagement_id, 1);
@@ -299,6 +2389,7 @@ mod test_legacy {
 
         assert_eq!(stored_escrow.client, client_address);
         assert_eq!(stored_escrow.artisan, artisan_address);
+        assert_eq!(stored_escrow.token, token_address);
         assert_eq!(stored_escrow.amount, amount);
         assert_eq!(stored_escrow.status, Status::Pending);
         assert_eq!(stored_escrow.deadline, deadline);
@@ -323,9 +2414,17 @@ mod test_legacy {
         let same_address = Address::generate(&env);
         let amount: i128 = 1000;
         let deadline = env.ledger().timestamp() + 86400;
+        let token_address = Address::generate(&env);
 
         // This should panic because client == artisan
-        client.initialize(&same_address, &same_address, &amount, &deadline);
+        client.initialize(
+            &same_address,
+            &same_address,
+            &amount,
+            &deadline,
+            &token_address,
+            &EngagementOptions::default(),
+        );
     }
 
     #[test]
@@ -339,9 +2438,17 @@ mod test_legacy {
         let artisan_address = Address::generate(&env);
         let zero_amount: i128 = 0;
         let deadline = env.ledger().timestamp() + 86400;
+        let token_address = Address::generate(&env);
 
         // This should panic because amount is zero
-        client.initialize(&client_address, &artisan_address, &zero_amount, &deadline);
+        client.initialize(
+            &client_address,
+            &artisan_address,
+            &zero_amount,
+            &deadline,
+            &token_address,
+            &EngagementOptions::default(),
+        );
     }
 
     #[test]
@@ -355,6 +2462,7 @@ mod test_legacy {
         let artisan_address = Address::generate(&env);
         let negative_amount: i128 = -100;
         let deadline = env.ledger().timestamp() + 86400;
+        let token_address = Address::generate(&env);
 
         // This should panic because amount is negative
         client.initialize(
@@ -362,6 +2470,8 @@ mod test_legacy {
             &artisan_address,
             &negative_amount,
             &deadline,
+            &token_address,
+            &EngagementOptions::default(),
         );
     }
 
@@ -403,9 +2513,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount: escrow_amount,
             status: Status::Pending,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
 
         // Store the escrow in contract storage
@@ -420,7 +2538,11 @@ mod test_legacy {
         assert_eq!(initial_contract_balance, 0);
 
         // Call deposit function as the client
-        EscrowContractClient::new(&env, &contract_id).deposit(&engagement_id, &token_address);
+        EscrowContractClient::new(&env, &contract_id).deposit(
+            &engagement_id,
+            &client_address,
+            &escrow_amount,
+        );
 
         // Verify contract's token balance increased
         let final_contract_balance = token_client.balance(&contract_id);
@@ -442,12 +2564,13 @@ mod test_legacy {
     }
 
     #[test]
+    #[should_panic]
     fn test_deposit_unauthorized_client() {
-        // Note: Current implementation doesn't verify caller authorization
-        // This test exists for completeness but doesn't enforce authorization
+        // A funder must authorize their own deposit; an address mocking auth
+        // as someone else cannot fund on another funder's behalf.
         let env = Env::default();
         let contract_id = env.register_contract(None, EscrowContract);
-        let _client = EscrowContractClient::new(&env, &contract_id);
+        let client = EscrowContractClient::new(&env, &contract_id);
 
         // Create test addresses
         let client_address = Address::generate(&env);
@@ -467,9 +2590,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount: escrow_amount,
             status: Status::Pending,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
 
         // Store the escrow
@@ -479,20 +2610,20 @@ mod test_legacy {
                 .set(&DataKey::Escrow(engagement_id), &escrow);
         });
 
-        // In current implementation, any address can call deposit
-        // This is a limitation that should be addressed in production
+        // Only the unauthorized address is mocked as authorizing, but the
+        // deposit call names `client_address` as the funder, so `from.require_auth()`
+        // has no matching mock and must panic.
         env.mock_auths(&[soroban_sdk::testutils::MockAuth {
             address: &unauthorized_address,
             invoke: &soroban_sdk::testutils::MockAuthInvoke {
                 contract: &contract_id,
                 fn_name: "deposit",
-                args: (engagement_id, token_address.clone()).into_val(&env),
+                args: (engagement_id, client_address.clone(), escrow_amount).into_val(&env),
                 sub_invokes: &[],
             },
         }]);
 
-        // This would work in current implementation (not ideal)
-        // client.deposit(&engagement_id, &token_address);
+        client.deposit(&engagement_id, &client_address, &escrow_amount);
     }
 
     #[test]
@@ -519,9 +2650,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount: escrow_amount,
             status: Status::Funded, // Already funded
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
 
         // Store the escrow
@@ -537,12 +2676,12 @@ mod test_legacy {
             invoke: &soroban_sdk::testutils::MockAuthInvoke {
                 contract: &contract_id,
                 fn_name: "deposit",
-                args: (engagement_id, token_address.clone()).into_val(&env),
+                args: (engagement_id, client_address.clone(), escrow_amount).into_val(&env),
                 sub_invokes: &[],
             },
         }]);
 
-        _client.deposit(&engagement_id, &token_address);
+        _client.deposit(&engagement_id, &client_address, &escrow_amount);
     }
 
     // New tests for deadline and reclaim behavior
@@ -573,9 +2712,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount: 500,
             status: Status::Pending,
             deadline: env.ledger().timestamp().saturating_sub(1),
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
         env.as_contract(&contract_id, || {
             env.storage()
@@ -584,7 +2731,7 @@ mod test_legacy {
         });
 
         // Attempt deposit - should panic due to expired deadline
-        client.deposit(&engagement_id, &token_address);
+        client.deposit(&engagement_id, &client_address, &500);
     }
 
     #[test]
@@ -615,9 +2762,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address.clone(),
+            token: token_address.clone(),
             amount,
             status: Status::Funded,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
         env.as_contract(&contract_id, || {
             env.storage()
@@ -627,7 +2782,7 @@ mod test_legacy {
         token_client.transfer(&client_address, &contract_id, &amount);
 
         // Releasing after deadline should panic
-        client.release(&engagement_id, &token_address);
+        client.release(&engagement_id);
     }
 
     #[test]
@@ -651,9 +2806,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount,
             status: Status::Funded,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
         env.as_contract(&contract_id, || {
             env.storage()
@@ -667,7 +2830,7 @@ mod test_legacy {
         token_contract_client.mint(&client_address, &amount);
         token_client.transfer(&client_address, &contract_id, &amount);
 
-        client.reclaim(&engagement_id, &token_address);
+        client.reclaim(&engagement_id);
     }
 
     #[test]
@@ -695,9 +2858,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount,
             status: Status::Funded,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
         env.as_contract(&contract_id, || {
             env.storage()
@@ -708,7 +2879,7 @@ mod test_legacy {
         token_client.transfer(&client_address, &contract_id, &amount);
 
         let before_balance = token_client.balance(&client_address);
-        client.reclaim(&engagement_id, &token_address);
+        client.reclaim(&engagement_id);
         let after_balance = token_client.balance(&client_address);
         assert_eq!(after_balance, before_balance + amount);
 
@@ -760,9 +2931,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount,
             status: Status::Funded,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
         env.as_contract(&contract_id, || {
             env.storage()
@@ -781,12 +2960,12 @@ mod test_legacy {
             invoke: &soroban_sdk::testutils::MockAuthInvoke {
                 contract: &contract_id,
                 fn_name: "reclaim",
-                args: (engagement_id, token_address.clone()).into_val(&env),
+                args: (engagement_id,).into_val(&env),
                 sub_invokes: &[],
             },
         }]);
 
-        client.reclaim(&engagement_id, &token_address);
+        client.reclaim(&engagement_id);
     }
 
     #[test]
@@ -810,9 +2989,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount,
             status: Status::Pending,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
         env.as_contract(&contract_id, || {
             env.storage()
@@ -820,7 +3007,7 @@ mod test_legacy {
                 .set(&DataKey::Escrow(engagement_id), &escrow);
         });
 
-        client.reclaim(&engagement_id, &token_address);
+        client.reclaim(&engagement_id);
     }
 
     #[test]
@@ -850,9 +3037,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount,
             status: Status::Funded,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
 
         env.as_contract(&contract_id, || {
@@ -864,9 +3059,9 @@ mod test_legacy {
         token_client.transfer(&client_address, &contract_id, &amount);
 
         // release back to artist first
-        client.release(&engagement_id, &token_address);
+        client.release(&engagement_id);
         // attempt reclaim after it's been released
-        client.reclaim(&engagement_id, &token_address);
+        client.reclaim(&engagement_id);
     }
 
     #[test]
@@ -899,9 +3094,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address.clone(),
+            token: token_address.clone(),
             amount,
             status: Status::Funded,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
 
         // Store the escrow
@@ -917,7 +3120,7 @@ mod test_legacy {
         assert_eq!(token_client.balance(&artisan_address), 0);
         assert_eq!(token_client.balance(&contract_id), amount);
 
-        client.release(&engagement_id, &token_address);
+        client.release(&engagement_id);
 
         assert_eq!(token_client.balance(&artisan_address), amount);
         assert_eq!(token_client.balance(&contract_id), 0);
@@ -945,9 +3148,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount,
             status: Status::Pending,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
 
         env.as_contract(&contract_id, || {
@@ -956,7 +3167,7 @@ mod test_legacy {
                 .set(&DataKey::Escrow(engagement_id), &escrow);
         });
 
-        client.release(&engagement_id, &token_address);
+        client.release(&engagement_id);
     }
 
     #[test]
@@ -985,9 +3196,17 @@ mod test_legacy {
         let escrow = Escrow {
             client: client_address.clone(),
             artisan: artisan_address,
+            token: token_address.clone(),
             amount,
             status: Status::Funded,
             deadline,
+            vesting: None,
+            released_so_far: 0,
+        arbiter: None,
+        destination: None,
+        staking_contract: None,
+        staked: false,
+        yield_beneficiary: None,
         };
 
         env.as_contract(&contract_id, || {
@@ -998,7 +3217,7 @@ mod test_legacy {
 
         token_client.transfer(&client_address, &contract_id, &amount);
 
-        client.release(&engagement_id, &token_address);
-        client.release(&engagement_id, &token_address);
+        client.release(&engagement_id);
+        client.release(&engagement_id);
     }
 }
@@ -1,12 +1,35 @@
 #![no_std]
 
-use soroban_sdk::{Address, Env, contract, contractimpl, contracttype};
+use soroban_sdk::{Address, Env, IntoVal, Vec, contract, contractimpl, contracttype};
+
+/// Event emitted on every successful `rate_artisan` call, carrying enough to
+/// reconstruct an artisan's rating history off-chain without the contract
+/// itself storing the full list of individual reviews.
+#[contracttype]
+pub struct RatingSubmittedEvent {
+    pub artisan: Address,
+    pub rater: Address,
+    pub stars: u32,
+    pub total_stars: u64,
+    pub review_count: u64,
+}
 
 /// Storage key for user reputation data
 #[contracttype]
 #[derive(Clone)]
 enum DataKey {
     Reputation(Address),
+    // Count of engagements an artisan has completed, as recorded by escrow
+    // contracts via `record_engagement` - distinct from `review_count`, since
+    // not every completed engagement is rated.
+    EngagementCount(Address),
+    // The star value a given rater most recently submitted for a given
+    // artisan, keyed by (artisan, rater). Lets `rate_artisan` detect a
+    // repeat rating and revise it in place instead of double-counting.
+    Rated(Address, Address),
+    // Address allowed to call `set_reputation` and the moderation entries.
+    // Set once via `initialize`, transferable via `transfer_admin`.
+    Admin,
 }
 
 /// Public struct containing aggregated review data for a user
@@ -15,6 +38,26 @@ enum DataKey {
 pub struct ReputationData {
     pub total_stars: u64,
     pub review_count: u64,
+    /// Time-decayed rating score, fixed-point scaled by `DECAY_SCALE`. Decays
+    /// geometrically towards zero the longer `last_update_ledger` ages, so a
+    /// long-inactive artisan's score fades even though `total_stars` does not.
+    pub decayed_score: u64,
+    /// Ledger timestamp (seconds) at which `decayed_score` was last brought
+    /// up to date.
+    pub last_update_ledger: u64,
+    /// Count of 1-star ratings received. `#[contracttype]` rejects fixed-size
+    /// arrays of non-`u8` element types, so the histogram is five scalar
+    /// fields rather than a `[u64; 5]`; `star_count_mut` indexes into them by
+    /// star value.
+    pub star_count_1: u64,
+    /// Count of 2-star ratings received.
+    pub star_count_2: u64,
+    /// Count of 3-star ratings received.
+    pub star_count_3: u64,
+    /// Count of 4-star ratings received.
+    pub star_count_4: u64,
+    /// Count of 5-star ratings received.
+    pub star_count_5: u64,
 }
 
 impl Default for ReputationData {
@@ -22,10 +65,75 @@ impl Default for ReputationData {
         ReputationData {
             total_stars: 0,
             review_count: 0,
+            decayed_score: 0,
+            last_update_ledger: 0,
+            star_count_1: 0,
+            star_count_2: 0,
+            star_count_3: 0,
+            star_count_4: 0,
+            star_count_5: 0,
         }
     }
 }
 
+/// Returns a mutable reference to the histogram bucket for `stars` (1..=5).
+fn star_count_mut(data: &mut ReputationData, stars: u32) -> &mut u64 {
+    match stars {
+        1 => &mut data.star_count_1,
+        2 => &mut data.star_count_2,
+        3 => &mut data.star_count_3,
+        4 => &mut data.star_count_4,
+        5 => &mut data.star_count_5,
+        _ => panic!("stars not in range"),
+    }
+}
+
+/// Collects the five scalar histogram fields into a `Vec<u64>` indexed by
+/// `stars - 1`, for returning over the contract boundary.
+fn star_count_vec(env: &Env, data: &ReputationData) -> Vec<u64> {
+    Vec::from_array(
+        env,
+        [
+            data.star_count_1,
+            data.star_count_2,
+            data.star_count_3,
+            data.star_count_4,
+            data.star_count_5,
+        ],
+    )
+}
+
+/// Fixed-point scale applied to `decayed_score` (stored value is the real
+/// score multiplied by this factor, to avoid floating point in `no_std`).
+const DECAY_SCALE: u64 = 100;
+
+/// Number of seconds of wall-clock time after which an untouched
+/// `decayed_score` is halved.
+const HALF_LIFE_SECONDS: u64 = 100_000;
+
+/// Applies geometric decay to `decayed_score` for the ledger-timestamp
+/// seconds elapsed since `last_update_ledger`, halving the score once per
+/// `HALF_LIFE_SECONDS` of whole half-lives, then approximating the
+/// remaining fractional half-life with a linear interpolation between that
+/// value and half of it — cheaper than a true exponential and `no_std`-safe,
+/// at the cost of a small kink at each half-life boundary. Without the
+/// remainder term the score would stay frozen until an entire half-life had
+/// elapsed instead of decaying continuously.
+fn apply_decay(decayed_score: u64, last_update_ledger: u64, current_timestamp: u64) -> u64 {
+    if current_timestamp <= last_update_ledger {
+        return decayed_score;
+    }
+    let elapsed = current_timestamp - last_update_ledger;
+    let periods = elapsed / HALF_LIFE_SECONDS;
+    if periods >= 64 {
+        return 0;
+    }
+    let after_periods = decayed_score >> periods;
+    let remainder = elapsed % HALF_LIFE_SECONDS;
+    let interpolated = (after_periods as u128 * remainder as u128) / (2 * HALF_LIFE_SECONDS as u128);
+    after_periods - interpolated as u64
+}
+
 /// Helper function to read reputation data for a user
 /// Returns default values (0 total_stars, 0 review_count) if user has no existing reputation
 pub fn read_reputation(env: &Env, user: &Address) -> ReputationData {
@@ -43,32 +151,208 @@ pub fn write_reputation(env: &Env, user: &Address, data: &ReputationData) {
     env.storage().persistent().set(&key, data);
 }
 
+/// Loads the configured admin and requires its authorization, panicking if
+/// `initialize` has never been called.
+fn require_admin(env: &Env) -> Address {
+    let admin: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Admin)
+        .expect("reputation contract not initialized");
+    admin.require_auth();
+    admin
+}
+
 #[contract]
 pub struct ReputationContract;
 
 #[contractimpl]
 impl ReputationContract {
+    /// One-time setup installing `admin` as the address allowed to call
+    /// `set_reputation` and the moderation entries. Panics if already
+    /// initialized.
+    pub fn initialize(env: Env, admin: Address) {
+        admin.require_auth();
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic!("reputation contract already initialized");
+        }
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+    }
+
+    /// Installs `new_admin` in place of the current admin. Requires the
+    /// current admin's authorization.
+    pub fn transfer_admin(env: Env, new_admin: Address) {
+        require_admin(&env);
+        env.storage().persistent().set(&DataKey::Admin, &new_admin);
+    }
+
     /// Get reputation data for a user
     pub fn get_reputation(env: Env, user: Address) -> ReputationData {
         read_reputation(&env, &user)
     }
 
-    /// Set reputation data for a user (for testing/admin purposes)
+    /// Set reputation data for a user. Restricted to the configured admin.
     pub fn set_reputation(env: Env, user: Address, data: ReputationData) {
+        require_admin(&env);
         write_reputation(&env, &user, &data);
     }
 
+    /// Moderation: remove `rater`'s submitted review from an artisan's
+    /// totals. The star value is read back from the same `Rated` entry
+    /// `rate_artisan` keys its revision logic on (rather than trusting an
+    /// admin-supplied value, which could drift from what's actually stored),
+    /// and that entry is then cleared so a later `rate_artisan` call from
+    /// `rater` is treated as a fresh rating instead of a revision against a
+    /// review that no longer exists — `rate_artisan`'s revision arithmetic
+    /// isn't saturating and would underflow against totals this call already
+    /// decremented. Decrements `total_stars`, `review_count`, and the
+    /// matching `get_distribution` bucket, and brings `decayed_score` up to
+    /// date before removing this review's scaled contribution from it — the
+    /// same bookkeeping `rate_artisan` does, kept consistent so
+    /// `get_decayed_stats`/`get_distribution` never diverge from the
+    /// corrected totals. Restricted to the configured admin. Saturates at
+    /// zero rather than panicking if the totals would otherwise underflow.
+    pub fn remove_review(env: Env, artisan: Address, rater: Address) {
+        require_admin(&env);
+        let rated_key = DataKey::Rated(artisan.clone(), rater);
+        let stars: u32 = env
+            .storage()
+            .persistent()
+            .get(&rated_key)
+            .unwrap_or_else(|| panic!("rater has not rated this artisan"));
+        let mut data = read_reputation(&env, &artisan);
+
+        let current_time = env.ledger().timestamp();
+        data.decayed_score = apply_decay(data.decayed_score, data.last_update_ledger, current_time);
+        data.decayed_score = data.decayed_score.saturating_sub(stars as u64 * DECAY_SCALE);
+        data.last_update_ledger = current_time;
+
+        data.total_stars = data.total_stars.saturating_sub(stars as u64);
+        data.review_count = data.review_count.saturating_sub(1);
+        let bucket = star_count_mut(&mut data, stars);
+        *bucket = bucket.saturating_sub(1);
+
+        env.storage().persistent().remove(&rated_key);
+        write_reputation(&env, &artisan, &data);
+    }
+
+    /// Moderation: wipe an artisan's reputation back to its default state.
+    /// Restricted to the configured admin.
+    pub fn reset_reputation(env: Env, artisan: Address) {
+        require_admin(&env);
+        write_reputation(&env, &artisan, &ReputationData::default());
+    }
+
     // update and persist an artisanâ€™s reputation score
-    pub fn rate_artisan(env: Env, artisan: Address, stars: u32) {
+    //
+    // `rater` must authorize the call. If `rater` has already rated this
+    // `artisan`, the previous star value is replaced rather than added again:
+    // `total_stars` is adjusted by the delta and `review_count` is left
+    // unchanged. `decayed_score` is brought up to date for the ledger-timestamp
+    // seconds elapsed since `last_update_ledger` before the new rating is folded in. The
+    // emitted `RatingSubmittedEvent` lets off-chain indexers attribute the
+    // rating and reconstruct history.
+    pub fn rate_artisan(env: Env, artisan: Address, stars: u32, rater: Address) {
         if stars < 1 || stars > 5 {
             panic!("stars not in range");
         }
+        rater.require_auth();
+
         let mut artisan_data = Self::get_reputation(env.clone(), artisan.clone());
-        artisan_data.total_stars += stars as u64;
-        artisan_data.review_count += 1;
+        let rated_key = DataKey::Rated(artisan.clone(), rater.clone());
+        let previous_stars: Option<u32> = env.storage().persistent().get(&rated_key);
+
+        let current_time = env.ledger().timestamp();
+        artisan_data.decayed_score = apply_decay(
+            artisan_data.decayed_score,
+            artisan_data.last_update_ledger,
+            current_time,
+        );
+
+        let stars_delta: i64 = match previous_stars {
+            Some(old_stars) => {
+                artisan_data.total_stars = artisan_data.total_stars + stars as u64 - old_stars as u64;
+                *star_count_mut(&mut artisan_data, old_stars) -= 1;
+                *star_count_mut(&mut artisan_data, stars) += 1;
+                stars as i64 - old_stars as i64
+            }
+            None => {
+                artisan_data.total_stars += stars as u64;
+                artisan_data.review_count += 1;
+                *star_count_mut(&mut artisan_data, stars) += 1;
+                stars as i64
+            }
+        };
+        let score_delta = stars_delta * DECAY_SCALE as i64;
+        artisan_data.decayed_score = if score_delta >= 0 {
+            artisan_data.decayed_score + score_delta as u64
+        } else {
+            artisan_data.decayed_score.saturating_sub((-score_delta) as u64)
+        };
+        artisan_data.last_update_ledger = current_time;
+
+        env.storage().persistent().set(&rated_key, &stars);
+        write_reputation(&env, &artisan, &artisan_data);
+
+        env.events().publish((), RatingSubmittedEvent {
+            artisan,
+            rater,
+            stars,
+            total_stars: artisan_data.total_stars,
+            review_count: artisan_data.review_count,
+        });
+    }
+
+    /// Get the time-decayed reputation for an artisan: `(decayed_score,
+    /// review_count, last_update_ledger)`. `decayed_score` is projected
+    /// forward to the current ledger timestamp without persisting the decay,
+    /// so repeated calls with no new rating keep returning a falling value.
+    /// Raw, non-decayed totals remain available via `get_reputation`.
+    pub fn get_decayed_stats(env: Env, artisan: Address) -> (u64, u64, u64) {
+        let data = read_reputation(&env, &artisan);
+        let current_time = env.ledger().timestamp();
+        let decayed_score = apply_decay(data.decayed_score, data.last_update_ledger, current_time);
+        (decayed_score, data.review_count, data.last_update_ledger)
+    }
+
+    /// Get the histogram of star values an artisan has received, as a 5-entry
+    /// vector indexed by `stars - 1` (index 0 is the 1-star bucket, index 4
+    /// the 5-star bucket).
+    pub fn get_distribution(env: Env, artisan: Address) -> Vec<u64> {
+        star_count_vec(&env, &read_reputation(&env, &artisan))
+    }
+
+    /// Get `(average_stars_scaled, review_count, star_counts)` for an
+    /// artisan in one call: `average_stars_scaled` is `total_stars * 100 /
+    /// review_count` (0 if there are no reviews yet), fixed-point scaled by
+    /// `DECAY_SCALE` like `decayed_score`. `star_counts` is the same 5-entry
+    /// vector returned by `get_distribution`.
+    pub fn get_stats_full(env: Env, artisan: Address) -> (u64, u64, Vec<u64>) {
+        let data = read_reputation(&env, &artisan);
+        let average_stars_scaled = if data.review_count == 0 {
+            0
+        } else {
+            data.total_stars * DECAY_SCALE / data.review_count
+        };
+        let star_counts = star_count_vec(&env, &data);
+        (average_stars_scaled, data.review_count, star_counts)
+    }
 
-        Self::set_reputation(env, artisan, artisan_data);
+    /// Record that an artisan has completed an engagement. Called by escrow
+    /// contracts on successful release; independent of `rate_artisan` since
+    /// a client isn't required to leave a rating.
+    pub fn record_engagement(env: Env, artisan: Address) {
+        let key = DataKey::EngagementCount(artisan);
+        let count: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(count + 1));
+    }
 
+    /// Get the number of completed engagements recorded for an artisan.
+    pub fn get_engagement_count(env: Env, artisan: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EngagementCount(artisan))
+            .unwrap_or(0)
     }
 }
 
@@ -102,13 +386,24 @@ mod tests {
     #[test]
     fn test_contract_set_and_get_reputation() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ReputationContract);
         let client = ReputationContractClient::new(&env, &contract_id);
 
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
         let user = Address::generate(&env);
         let data = ReputationData {
             total_stars: 100,
             review_count: 20,
+            decayed_score: 0,
+            last_update_ledger: 0,
+            star_count_1: 0,
+            star_count_2: 0,
+            star_count_3: 0,
+            star_count_4: 0,
+            star_count_5: 0,
         };
 
         // Test write_reputation helper through contract
@@ -124,19 +419,37 @@ mod tests {
     #[test]
     fn test_multiple_users_independent_reputation() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ReputationContract);
         let client = ReputationContractClient::new(&env, &contract_id);
 
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
         let user1 = Address::generate(&env);
         let user2 = Address::generate(&env);
 
         let data1 = ReputationData {
             total_stars: 50,
             review_count: 10,
+            decayed_score: 0,
+            last_update_ledger: 0,
+            star_count_1: 0,
+            star_count_2: 0,
+            star_count_3: 0,
+            star_count_4: 0,
+            star_count_5: 0,
         };
         let data2 = ReputationData {
             total_stars: 75,
             review_count: 15,
+            decayed_score: 0,
+            last_update_ledger: 0,
+            star_count_1: 0,
+            star_count_2: 0,
+            star_count_3: 0,
+            star_count_4: 0,
+            star_count_5: 0,
         };
 
         client.set_reputation(&user1, &data1);
@@ -154,15 +467,26 @@ mod tests {
     #[test]
     fn test_update_existing_reputation() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ReputationContract);
         let client = ReputationContractClient::new(&env, &contract_id);
 
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
         let user = Address::generate(&env);
 
         // Set initial reputation
         let initial_data = ReputationData {
             total_stars: 30,
             review_count: 5,
+            decayed_score: 0,
+            last_update_ledger: 0,
+            star_count_1: 0,
+            star_count_2: 0,
+            star_count_3: 0,
+            star_count_4: 0,
+            star_count_5: 0,
         };
         client.set_reputation(&user, &initial_data);
 
@@ -170,6 +494,13 @@ mod tests {
         let updated_data = ReputationData {
             total_stars: 80,
             review_count: 12,
+            decayed_score: 0,
+            last_update_ledger: 0,
+            star_count_1: 0,
+            star_count_2: 0,
+            star_count_3: 0,
+            star_count_4: 0,
+            star_count_5: 0,
         };
         client.set_reputation(&user, &updated_data);
 
@@ -181,11 +512,13 @@ mod tests {
      #[test]
     fn test_rate_artisan() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ReputationContract);
         let client = ReputationContractClient::new(&env, &contract_id);
 
         let artisan = Address::generate(&env);
-        let _ = client.rate_artisan(&artisan, &2);
+        let rater = Address::generate(&env);
+        let _ = client.rate_artisan(&artisan, &2, &rater);
         let reputation = client.get_reputation(&artisan);
 
         // Verifies that read_reputation returns default values (0, 0) when no reputation exists
@@ -197,37 +530,435 @@ mod tests {
     #[should_panic(expected = "stars not in range")]
     fn test_rate_artisan_not_in_range() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ReputationContract);
         let client = ReputationContractClient::new(&env, &contract_id);
 
         let artisan = Address::generate(&env);
-        let _ = client.rate_artisan(&artisan, &6);
+        let rater = Address::generate(&env);
+        let _ = client.rate_artisan(&artisan, &6, &rater);
     }
 
     #[test]
     #[should_panic(expected = "stars not in range")]
     fn test_rate_artisan_not_in_range_zero() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ReputationContract);
         let client = ReputationContractClient::new(&env, &contract_id);
 
         let artisan = Address::generate(&env);
-        let _ = client.rate_artisan(&artisan, &0);
+        let rater = Address::generate(&env);
+        let _ = client.rate_artisan(&artisan, &0, &rater);
     }
 
     #[test]
     fn test_rate_artisan_multiple() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ReputationContract);
         let client = ReputationContractClient::new(&env, &contract_id);
 
         let artisan = Address::generate(&env);
-        let _ = client.rate_artisan(&artisan, &2);
-        let _ = client.rate_artisan(&artisan, &5);
-        let _ = client.rate_artisan(&artisan, &1);
+        let rater_one = Address::generate(&env);
+        let rater_two = Address::generate(&env);
+        let rater_three = Address::generate(&env);
+        let _ = client.rate_artisan(&artisan, &2, &rater_one);
+        let _ = client.rate_artisan(&artisan, &5, &rater_two);
+        let _ = client.rate_artisan(&artisan, &1, &rater_three);
         let reputation = client.get_reputation(&artisan);
 
         assert_eq!(reputation.total_stars, 8);
         assert_eq!(reputation.review_count, 3);
     }
+
+    /// Test: `rate_artisan` publishes a `RatingSubmittedEvent` carrying the
+    /// artisan, rater, star value, and post-update totals after a single rating.
+    #[test]
+    fn test_rate_artisan_emits_event() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &4, &rater);
+
+        let published = env.events().all();
+        let (_, _, data) = published.last().unwrap();
+        let event: RatingSubmittedEvent = data.into_val(&env);
+
+        assert_eq!(event.artisan, artisan);
+        assert_eq!(event.rater, rater);
+        assert_eq!(event.stars, 4);
+        assert_eq!(event.total_stars, 4);
+        assert_eq!(event.review_count, 1);
+    }
+
+    /// Test: a second rating on the same artisan emits another event with the
+    /// running totals, not just the latest rating's own values.
+    #[test]
+    fn test_rate_artisan_emits_event_on_subsequent_ratings() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let first_rater = Address::generate(&env);
+        let second_rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &3, &first_rater);
+        client.rate_artisan(&artisan, &5, &second_rater);
+
+        let published = env.events().all();
+        let (_, _, data) = published.last().unwrap();
+        let event: RatingSubmittedEvent = data.into_val(&env);
+
+        assert_eq!(event.artisan, artisan);
+        assert_eq!(event.rater, second_rater);
+        assert_eq!(event.stars, 5);
+        assert_eq!(event.total_stars, 8);
+        assert_eq!(event.review_count, 2);
+    }
+
+    /// Test: a rater revising their own rating updates `total_stars` by the
+    /// delta between old and new star values without incrementing `review_count`.
+    #[test]
+    fn test_rate_artisan_revises_existing_rating() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &2, &rater);
+        client.rate_artisan(&artisan, &5, &rater);
+        let reputation = client.get_reputation(&artisan);
+
+        assert_eq!(reputation.total_stars, 5);
+        assert_eq!(reputation.review_count, 1);
+    }
+
+    /// Test: revising a rating down lowers `total_stars` accordingly, still
+    /// without touching `review_count`.
+    #[test]
+    fn test_rate_artisan_revision_can_lower_total() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &5, &rater);
+        client.rate_artisan(&artisan, &1, &rater);
+        let reputation = client.get_reputation(&artisan);
+
+        assert_eq!(reputation.total_stars, 1);
+        assert_eq!(reputation.review_count, 1);
+    }
+
+    /// Test: a rating call is rejected if `rater` has not authorized it.
+    #[test]
+    #[should_panic]
+    fn test_rate_artisan_requires_rater_auth() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &3, &rater);
+    }
+
+    /// Test: with no ledgers elapsed, `decayed_score` simply accumulates the
+    /// scaled star contributions from each rating.
+    #[test]
+    fn test_get_decayed_stats_no_elapsed_time() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &4, &rater);
+
+        let (decayed_score, review_count, _) = client.get_decayed_stats(&artisan);
+        assert_eq!(decayed_score, 4 * DECAY_SCALE);
+        assert_eq!(review_count, 1);
+    }
+
+    /// Test: once a full half-life's worth of wall-clock time has elapsed
+    /// with no new rating, `get_decayed_stats` reports the score halved.
+    #[test]
+    fn test_get_decayed_stats_halves_after_half_life() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &4, &rater);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += HALF_LIFE_SECONDS;
+        });
+
+        let (decayed_score, review_count, _) = client.get_decayed_stats(&artisan);
+        assert_eq!(decayed_score, (4 * DECAY_SCALE) / 2);
+        assert_eq!(review_count, 1);
+    }
+
+    /// Test: part-way through a half-life, the score sits strictly between
+    /// its un-decayed value and the fully-halved value, reflecting the
+    /// linear remainder interpolation rather than staying frozen until the
+    /// whole half-life elapses.
+    #[test]
+    fn test_get_decayed_stats_interpolates_within_half_life() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &4, &rater);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += HALF_LIFE_SECONDS / 2;
+        });
+
+        let (decayed_score, _, _) = client.get_decayed_stats(&artisan);
+        assert!(decayed_score > (4 * DECAY_SCALE) / 2);
+        assert!(decayed_score < 4 * DECAY_SCALE);
+    }
+
+    /// Test: `get_reputation`'s raw `total_stars` is unaffected by the passage
+    /// of time — only `get_decayed_stats` decays.
+    #[test]
+    fn test_raw_reputation_does_not_decay() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &4, &rater);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += HALF_LIFE_SECONDS;
+        });
+
+        let reputation = client.get_reputation(&artisan);
+        assert_eq!(reputation.total_stars, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "reputation contract not initialized")]
+    fn test_set_reputation_requires_initialization() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        client.set_reputation(&user, &ReputationData::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "reputation contract already initialized")]
+    fn test_initialize_rejects_second_call() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.initialize(&admin);
+    }
+
+    #[test]
+    fn test_transfer_admin_lets_new_admin_set_reputation() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let new_admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.transfer_admin(&new_admin);
+
+        let user = Address::generate(&env);
+        let data = ReputationData {
+            total_stars: 10,
+            review_count: 2,
+            decayed_score: 0,
+            last_update_ledger: 0,
+            star_count_1: 0,
+            star_count_2: 0,
+            star_count_3: 0,
+            star_count_4: 0,
+            star_count_5: 0,
+        };
+        client.set_reputation(&user, &data);
+        assert_eq!(client.get_reputation(&user).total_stars, 10);
+    }
+
+    #[test]
+    fn test_remove_review_reverts_last_rating_counted() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &5, &rater);
+        client.remove_review(&artisan, &rater);
+
+        let reputation = client.get_reputation(&artisan);
+        assert_eq!(reputation.total_stars, 0);
+        assert_eq!(reputation.review_count, 0);
+
+        let (decayed_score, review_count, _) = client.get_decayed_stats(&artisan);
+        assert_eq!(decayed_score, 0);
+        assert_eq!(review_count, 0);
+
+        let distribution = client.get_distribution(&artisan);
+        assert_eq!(distribution, Vec::from_array(&env, [0, 0, 0, 0, 0]));
+    }
+
+    /// A rater whose review was moderated away via `remove_review` should be
+    /// able to rate again as if for the first time: `remove_review` must
+    /// clear the `Rated` entry it keys its lookup on, or `rate_artisan`'s
+    /// revision branch would underflow subtracting against totals that are
+    /// already back at zero.
+    #[test]
+    fn test_rate_after_remove_review_is_treated_as_fresh() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &5, &rater);
+        client.remove_review(&artisan, &rater);
+
+        client.rate_artisan(&artisan, &3, &rater);
+
+        let reputation = client.get_reputation(&artisan);
+        assert_eq!(reputation.total_stars, 3);
+        assert_eq!(reputation.review_count, 1);
+
+        let distribution = client.get_distribution(&artisan);
+        assert_eq!(distribution, Vec::from_array(&env, [0, 0, 1, 0, 0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "rater has not rated this artisan")]
+    fn test_remove_review_rejects_unrated_rater() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.remove_review(&artisan, &rater);
+    }
+
+    #[test]
+    fn test_reset_reputation_clears_all_fields() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &5, &rater);
+        client.reset_reputation(&artisan);
+
+        let reputation = client.get_reputation(&artisan);
+        assert_eq!(reputation, ReputationData::default());
+    }
+
+    /// Test: `get_distribution` tallies each rater's star value into the
+    /// matching bucket.
+    #[test]
+    fn test_get_distribution_tallies_buckets() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater_one = Address::generate(&env);
+        let rater_two = Address::generate(&env);
+        let rater_three = Address::generate(&env);
+        client.rate_artisan(&artisan, &5, &rater_one);
+        client.rate_artisan(&artisan, &5, &rater_two);
+        client.rate_artisan(&artisan, &3, &rater_three);
+
+        let distribution = client.get_distribution(&artisan);
+        assert_eq!(distribution, Vec::from_array(&env, [0, 0, 1, 0, 2]));
+    }
+
+    /// Test: revising an existing rating moves its tally from the old bucket
+    /// to the new one rather than leaving a stale count behind.
+    #[test]
+    fn test_get_distribution_reflects_revised_rating() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater = Address::generate(&env);
+        client.rate_artisan(&artisan, &2, &rater);
+        client.rate_artisan(&artisan, &4, &rater);
+
+        let distribution = client.get_distribution(&artisan);
+        assert_eq!(distribution, Vec::from_array(&env, [0, 0, 0, 1, 0]));
+    }
+
+    /// Test: `get_stats_full` reports the scaled average alongside the
+    /// review count and histogram.
+    #[test]
+    fn test_get_stats_full_reports_scaled_average() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ReputationContract);
+        let client = ReputationContractClient::new(&env, &contract_id);
+
+        let artisan = Address::generate(&env);
+        let rater_one = Address::generate(&env);
+        let rater_two = Address::generate(&env);
+        client.rate_artisan(&artisan, &2, &rater_one);
+        client.rate_artisan(&artisan, &4, &rater_two);
+
+        let (average_scaled, review_count, distribution) = client.get_stats_full(&artisan);
+        assert_eq!(average_scaled, 300);
+        assert_eq!(review_count, 2);
+        assert_eq!(distribution, Vec::from_array(&env, [0, 1, 0, 1, 0]));
+    }
 }